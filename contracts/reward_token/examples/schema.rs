@@ -7,7 +7,10 @@ use cw20::{
     AllAccountsResponse, AllAllowancesResponse, AllowanceResponse, BalanceResponse,
     TokenInfoResponse,
 };
-use cw20_legacy::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
+use cw20_legacy::msg::InstantiateMsg;
+use gohm_staking::reward_token::{
+    BackingResponse, ExecuteMsg, QueryMsg, RedemptionPreviewResponse,
+};
 
 fn main() {
     let mut out_dir = current_dir().unwrap();
@@ -23,4 +26,6 @@ fn main() {
     export_schema(&schema_for!(TokenInfoResponse), &out_dir);
     export_schema(&schema_for!(AllAllowancesResponse), &out_dir);
     export_schema(&schema_for!(AllAccountsResponse), &out_dir);
+    export_schema(&schema_for!(BackingResponse), &out_dir);
+    export_schema(&schema_for!(RedemptionPreviewResponse), &out_dir);
 }
@@ -1,21 +1,28 @@
+use cosmwasm_bignumber::{Decimal256, Uint256};
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    to_binary, Binary, CosmosMsg, Deps, DepsMut, Env, MessageInfo, Response, StdError, StdResult,
-    Uint128, WasmMsg,
+    from_binary, to_binary, Binary, CanonicalAddr, Coin, CosmosMsg, Decimal, Deps, DepsMut, Env,
+    MessageInfo, Order, Response, StdError, StdResult, Storage, Uint128, WasmMsg,
 };
 
-use crate::state::{Config, CONFIGURATION};
-use cw2::set_contract_version;
+use crate::migration::{parse_version, run_migrations};
+use crate::state::{BasketAssetRaw, Config, CONFIGURATION, DEPOSITS};
+use cw2::{get_contract_version, set_contract_version};
+use cw20::Cw20ReceiveMsg;
 use cw20_legacy::{
     contract::{execute as cw20_execute, execute_burn, execute_mint, query as cw20_query},
-    msg::{ExecuteMsg, QueryMsg},
+    msg::{ExecuteMsg as Cw20ExecuteMsg, QueryMsg as Cw20QueryMsg},
     state::{MinterData, TokenInfo, TOKEN_INFO},
     ContractError,
 };
-use terraswap::asset::{Asset, AssetInfo};
+use gohm_staking::asset::{Asset, AssetInfo};
 
-use gohm_staking::reward_token::{InstantiateMsg, MigrateMsg};
+use gohm_staking::querier::{query_balance, query_token_balance};
+use gohm_staking::reward_token::{
+    AssetBackingResponse, BackingResponse, BasketAsset, Cw20HookMsg, DepositsResponse, ExecuteMsg,
+    InstantiateMsg, MigrateMsg, QueryMsg, RedemptionMode, RedemptionPreviewResponse,
+};
 
 // version info for migration info
 const CONTRACT_NAME: &str = "crates.io:cw20-base";
@@ -48,13 +55,21 @@ pub fn instantiate(
 
     TOKEN_INFO.save(deps.storage, &data)?;
 
+    let admin = match msg.admin {
+        Some(admin) => Some(deps.api.addr_canonicalize(&admin)?),
+        None => None,
+    };
+    let basket = msg
+        .basket
+        .iter()
+        .map(|asset| BasketAssetRaw::from_normal(deps.api, asset))
+        .collect::<StdResult<Vec<BasketAssetRaw>>>()?;
     CONFIGURATION.save(
         deps.storage,
         &Config {
-            gohm_token: deps.api.addr_canonicalize(&msg.gohm_token)?,
-            denom: msg.denom,
-            gohm_rate: msg.gohm_rate,
-            denom_rate: msg.denom_rate,
+            basket,
+            admin,
+            redemption_mode: RedemptionMode::Fixed,
         },
     )?;
     Ok(Response::default())
@@ -68,63 +83,278 @@ pub fn execute(
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
     match msg {
-        ExecuteMsg::Mint { recipient, amount } => try_mint(deps, env, info, recipient, amount),
+        ExecuteMsg::Mint { recipient, amount } => {
+            try_mint(deps, env, info, recipient, amount, None)
+        }
+        ExecuteMsg::Receive(msg) => receive_cw20(deps, env, info, msg),
         ExecuteMsg::Burn { amount } => try_burn(deps, env, info, amount),
-        _ => cw20_execute(deps, env, info, msg),
+        ExecuteMsg::UpdateRates { basket } => try_update_rates(deps, info, basket),
+        ExecuteMsg::SetRedemptionMode { mode } => try_set_redemption_mode(deps, info, mode),
+        ExecuteMsg::DepositNative {} => try_deposit_native(deps, info),
+        ExecuteMsg::WithdrawDeposits {} => try_withdraw_deposits(deps, info),
+        ExecuteMsg::Transfer { recipient, amount } => cw20_execute(
+            deps,
+            env,
+            info,
+            Cw20ExecuteMsg::Transfer { recipient, amount },
+        ),
+        ExecuteMsg::Send {
+            contract,
+            amount,
+            msg,
+        } => cw20_execute(
+            deps,
+            env,
+            info,
+            Cw20ExecuteMsg::Send {
+                contract,
+                amount,
+                msg,
+            },
+        ),
+        ExecuteMsg::IncreaseAllowance {
+            spender,
+            amount,
+            expires,
+        } => cw20_execute(
+            deps,
+            env,
+            info,
+            Cw20ExecuteMsg::IncreaseAllowance {
+                spender,
+                amount,
+                expires,
+            },
+        ),
+        ExecuteMsg::DecreaseAllowance {
+            spender,
+            amount,
+            expires,
+        } => cw20_execute(
+            deps,
+            env,
+            info,
+            Cw20ExecuteMsg::DecreaseAllowance {
+                spender,
+                amount,
+                expires,
+            },
+        ),
+        ExecuteMsg::TransferFrom {
+            owner,
+            recipient,
+            amount,
+        } => cw20_execute(
+            deps,
+            env,
+            info,
+            Cw20ExecuteMsg::TransferFrom {
+                owner,
+                recipient,
+                amount,
+            },
+        ),
+        ExecuteMsg::SendFrom {
+            owner,
+            contract,
+            amount,
+            msg,
+        } => cw20_execute(
+            deps,
+            env,
+            info,
+            Cw20ExecuteMsg::SendFrom {
+                owner,
+                contract,
+                amount,
+                msg,
+            },
+        ),
+        ExecuteMsg::BurnFrom { owner, amount } => {
+            cw20_execute(deps, env, info, Cw20ExecuteMsg::BurnFrom { owner, amount })
+        }
     }
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
-    cw20_query(deps, env, msg)
+    let cw20_msg = match msg {
+        QueryMsg::Backing {} => return to_binary(&query_backing(deps, env)?),
+        QueryMsg::RedemptionPreview { amount } => {
+            return to_binary(&query_redemption_preview(deps, env, amount)?)
+        }
+        QueryMsg::Deposits { address } => return to_binary(&query_deposits(deps, address)?),
+        QueryMsg::Balance { address } => Cw20QueryMsg::Balance { address },
+        QueryMsg::TokenInfo {} => Cw20QueryMsg::TokenInfo {},
+        QueryMsg::Minter {} => Cw20QueryMsg::Minter {},
+        QueryMsg::Allowance { owner, spender } => Cw20QueryMsg::Allowance { owner, spender },
+        QueryMsg::AllAllowances {
+            owner,
+            start_after,
+            limit,
+        } => Cw20QueryMsg::AllAllowances {
+            owner,
+            start_after,
+            limit,
+        },
+        QueryMsg::AllAccounts { start_after, limit } => {
+            Cw20QueryMsg::AllAccounts { start_after, limit }
+        }
+    };
+    cw20_query(deps, env, cw20_msg)
 }
 
+// `received` is a basket cw20 that already arrived through a cw20 Send
 fn try_mint(
     deps: DepsMut,
     env: Env,
     info: MessageInfo,
     recipient: String,
     amount: Uint128,
+    received: Option<Asset>,
 ) -> Result<Response, ContractError> {
-    let config = CONFIGURATION.load(deps.storage)?;
+    let basket = load_basket(deps.as_ref())?;
 
-    if info.funds.len() > 1 {
-        return Err(ContractError::Std(StdError::generic_err(
-            "Cannot receive several denoms",
-        )));
+    // every coin sent must be a native basket asset
+    for coin in info.funds.iter() {
+        if !basket.iter().any(|asset| match &asset.info {
+            AssetInfo::NativeToken { denom } => *denom == coin.denom,
+            AssetInfo::Token { .. } => false,
+        }) {
+            return Err(ContractError::Std(StdError::generic_err(format!(
+                "{} is not in the basket",
+                coin.denom
+            ))));
+        }
     }
-    let denom_amount: Uint128 = info
-        .funds
-        .iter()
-        .find(|c| c.denom == *config.denom)
-        .map(|c| Uint128::from(c.amount))
-        .unwrap_or_else(Uint128::zero);
 
-    if amount * config.denom_rate != denom_amount {
-        return Err(ContractError::Std(StdError::generic_err(
-            "Invalid denom amount",
-        )));
-    }
+    let mut messages: Vec<CosmosMsg> = vec![];
+    for asset in basket {
+        let required_amount = amount * asset.rate;
+        match asset.info {
+            AssetInfo::NativeToken { denom } => {
+                let denom_amount: Uint128 = info
+                    .funds
+                    .iter()
+                    .find(|c| c.denom == denom)
+                    .map(|c| c.amount)
+                    .unwrap_or_else(Uint128::zero);
 
-    let gohm_amount = amount * config.gohm_rate;
-    let gohm_token = deps.api.addr_humanize(&config.gohm_token)?.to_string();
+                if required_amount != denom_amount {
+                    return Err(ContractError::Std(StdError::generic_err(
+                        "Invalid denom amount",
+                    )));
+                }
+            }
+            AssetInfo::Token { contract_addr } => {
+                let received_amount = match &received {
+                    Some(Asset {
+                        info:
+                            AssetInfo::Token {
+                                contract_addr: token,
+                            },
+                        amount,
+                    }) if *token == contract_addr => Some(*amount),
+                    _ => None,
+                };
+                if let Some(received_amount) = received_amount {
+                    if required_amount != received_amount {
+                        return Err(ContractError::Std(StdError::generic_err(
+                            "Invalid token amount",
+                        )));
+                    }
+                } else if !required_amount.is_zero() {
+                    messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
+                        contract_addr,
+                        msg: to_binary(&Cw20ExecuteMsg::TransferFrom {
+                            owner: info.sender.to_string(),
+                            recipient: env.contract.address.to_string(),
+                            amount: required_amount,
+                        })?,
+                        funds: vec![],
+                    }));
+                }
+            }
+        }
+    }
 
-    let execute_res = execute_mint(deps, env.clone(), info.clone(), recipient, amount);
+    let execute_res = execute_mint(deps, env, info, recipient, amount);
     match execute_res {
-        Ok(response) => Ok(if gohm_amount.is_zero() {
-            response
-        } else {
-            response.add_message(CosmosMsg::Wasm(WasmMsg::Execute {
-                contract_addr: gohm_token,
-                msg: to_binary(&ExecuteMsg::TransferFrom {
-                    owner: info.sender.to_string(),
-                    recipient: env.contract.address.to_string(),
-                    amount: gohm_amount,
-                })?,
-                funds: vec![],
-            }))
-        }),
-        Err(err) => return Err(err),
+        Ok(response) => Ok(response.add_messages(messages)),
+        Err(err) => Err(err),
+    }
+}
+
+fn receive_cw20(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    cw20_msg: Cw20ReceiveMsg,
+) -> Result<Response, ContractError> {
+    match from_binary(&cw20_msg.msg)? {
+        Cw20HookMsg::MintHook { recipient } => {
+            let token = AssetInfo::Token {
+                contract_addr: info.sender.to_string(),
+            };
+            let basket = load_basket(deps.as_ref())?;
+            let rate = basket
+                .iter()
+                .find(|asset| asset.info.equal(&token))
+                .map(|asset| asset.rate)
+                .unwrap_or_else(Decimal::zero);
+            if rate.is_zero() {
+                return Err(ContractError::Std(StdError::generic_err(format!(
+                    "{} is not in the basket",
+                    info.sender
+                ))));
+            }
+
+            // the minter is whoever sent the tokens; whatever doesn't make up
+            // a whole reward token goes back to them
+            let minter = deps.api.addr_validate(&cw20_msg.sender)?;
+            let amount: Uint128 = (Uint256::from(cw20_msg.amount) / Decimal256::from(rate)).into();
+            let used_amount = amount * rate;
+            let refund = Asset {
+                info: token.clone(),
+                amount: cw20_msg.amount - used_amount,
+            };
+
+            // a cw20 Send carries no coins, so native assets come from deposits
+            let depositor = deps.api.addr_canonicalize(minter.as_str())?;
+            let mut funds: Vec<Coin> = vec![];
+            for asset in basket.iter() {
+                let required_amount = amount * asset.rate;
+                if let AssetInfo::NativeToken { denom } = &asset.info {
+                    if !required_amount.is_zero() {
+                        use_deposit(deps.storage, &depositor, denom, required_amount)?;
+                        funds.push(Coin {
+                            denom: denom.to_string(),
+                            amount: required_amount,
+                        });
+                    }
+                }
+            }
+
+            let minter_info = MessageInfo {
+                sender: minter.clone(),
+                funds,
+            };
+            let mut response = try_mint(
+                deps.branch(),
+                env,
+                minter_info,
+                recipient,
+                amount,
+                Some(Asset {
+                    info: token,
+                    amount: used_amount,
+                }),
+            )?;
+            if !refund.amount.is_zero() {
+                response = response.add_message(refund.into_msg(&deps.querier, minter)?);
+            }
+            Ok(response)
+        }
     }
 }
 
@@ -135,42 +365,346 @@ fn try_burn(
     amount: Uint128,
 ) -> Result<Response, ContractError> {
     let config = CONFIGURATION.load(deps.storage)?;
-
-    let denom_amount = amount * config.denom_rate;
-    let gohm_amount = amount * config.gohm_rate;
-    let gohm_token = deps.api.addr_humanize(&config.gohm_token)?.to_string();
+    let assets = redemption_assets(deps.as_ref(), &env, &config, amount)?;
 
     let querier = deps.querier;
     let execute_res = execute_burn(deps, env.clone(), info.clone(), amount);
     match execute_res {
         Ok(response) => {
             let mut messages: Vec<CosmosMsg> = vec![];
-
-            if !denom_amount.is_zero() {
-                let denom_asset = Asset {
-                    info: AssetInfo::NativeToken {
-                        denom: config.denom,
-                    },
-                    amount: denom_amount,
-                };
-                messages.push(denom_asset.into_msg(&querier, info.sender.clone())?);
-            }
-            if !gohm_amount.is_zero() {
-                let gohm_asset = Asset {
-                    info: AssetInfo::Token {
-                        contract_addr: gohm_token,
-                    },
-                    amount: gohm_amount,
-                };
-                messages.push(gohm_asset.into_msg(&querier, info.sender)?);
+            for asset in assets {
+                if !asset.amount.is_zero() {
+                    messages.push(asset.into_msg(&querier, info.sender.clone())?);
+                }
             }
             Ok(response.add_messages(messages))
         }
-        Err(err) => return Err(err),
+        Err(err) => Err(err),
+    }
+}
+
+// basket assets paid out for burning `amount`
+fn redemption_assets(
+    deps: Deps,
+    env: &Env,
+    config: &Config,
+    amount: Uint128,
+) -> StdResult<Vec<Asset>> {
+    let total_supply = TOKEN_INFO.load(deps.storage)?.total_supply;
+
+    let mut assets: Vec<Asset> = vec![];
+    for asset in config.basket.iter() {
+        let asset = asset.to_normal(deps.api)?;
+        let reserve = query_reserve(deps, env, &asset.info)?;
+        let amount = match config.redemption_mode {
+            RedemptionMode::Fixed => burn_payout(amount, asset.rate, reserve, total_supply),
+            RedemptionMode::ProRata => pro_rata_payout(amount, reserve, total_supply),
+        };
+        assets.push(Asset {
+            info: asset.info,
+            amount,
+        });
+    }
+    Ok(assets)
+}
+
+// takes `amount` of `denom` out of the depositor's balance
+fn use_deposit(
+    storage: &mut dyn Storage,
+    depositor: &CanonicalAddr,
+    denom: &str,
+    amount: Uint128,
+) -> StdResult<()> {
+    let key = (depositor.as_slice(), denom);
+    let deposit = DEPOSITS.may_load(storage, key)?.unwrap_or_default();
+    if deposit < amount {
+        return Err(StdError::generic_err(format!(
+            "Insufficient {} deposit",
+            denom
+        )));
+    }
+    if deposit == amount {
+        DEPOSITS.remove(storage, key);
+    } else {
+        DEPOSITS.save(storage, key, &(deposit - amount))?;
+    }
+    Ok(())
+}
+
+fn load_deposits(storage: &dyn Storage, depositor: &CanonicalAddr) -> StdResult<Vec<Coin>> {
+    DEPOSITS
+        .prefix(depositor.as_slice())
+        .range(storage, None, None, Order::Ascending)
+        .map(|item| {
+            let (denom, amount) = item?;
+            Ok(Coin {
+                denom: String::from_utf8(denom)
+                    .map_err(|_| StdError::invalid_utf8("deposit denom"))?,
+                amount,
+            })
+        })
+        .collect()
+}
+
+fn load_basket(deps: Deps) -> StdResult<Vec<BasketAsset>> {
+    CONFIGURATION
+        .load(deps.storage)?
+        .basket
+        .iter()
+        .map(|asset| asset.to_normal(deps.api))
+        .collect()
+}
+
+// balance of a basket asset held by this contract
+fn query_reserve(deps: Deps, env: &Env, info: &AssetInfo) -> StdResult<Uint128> {
+    match info {
+        AssetInfo::Token { contract_addr } => query_token_balance(
+            deps,
+            deps.api.addr_validate(contract_addr)?,
+            env.contract.address.clone(),
+        ),
+        AssetInfo::NativeToken { denom } => {
+            query_balance(deps, env.contract.address.clone(), denom.to_string())
+        }
+    }
+}
+
+// pay `amount * rate`, but never more than the burned share of the reserve,
+// so burns stay solvent whatever rates the outstanding supply was minted at
+pub fn burn_payout(
+    amount: Uint128,
+    rate: Decimal,
+    reserve: Uint128,
+    total_supply: Uint128,
+) -> Uint128 {
+    if total_supply.is_zero() {
+        return Uint128::zero();
+    }
+    std::cmp::min(amount * rate, amount.multiply_ratio(reserve, total_supply))
+}
+
+// pay the burned share of the reserve whatever the rates are
+pub fn pro_rata_payout(amount: Uint128, reserve: Uint128, total_supply: Uint128) -> Uint128 {
+    if total_supply.is_zero() {
+        return Uint128::zero();
+    }
+    amount.multiply_ratio(reserve, total_supply)
+}
+
+fn try_update_rates(
+    deps: DepsMut,
+    info: MessageInfo,
+    basket: Vec<BasketAsset>,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIGURATION.load(deps.storage)?;
+    if config.admin != Some(deps.api.addr_canonicalize(info.sender.as_str())?) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    for asset in basket.iter() {
+        let asset = BasketAssetRaw::from_normal(deps.api, asset)?;
+        match config
+            .basket
+            .iter_mut()
+            .find(|existing| existing.info.equal(&asset.info))
+        {
+            Some(existing) => existing.rate = asset.rate,
+            None => config.basket.push(asset),
+        }
+    }
+    CONFIGURATION.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attribute("action", "update_rates"))
+}
+
+fn try_set_redemption_mode(
+    deps: DepsMut,
+    info: MessageInfo,
+    mode: RedemptionMode,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIGURATION.load(deps.storage)?;
+    if config.admin != Some(deps.api.addr_canonicalize(info.sender.as_str())?) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    config.redemption_mode = mode;
+    CONFIGURATION.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "set_redemption_mode"),
+        (
+            "mode",
+            match config.redemption_mode {
+                RedemptionMode::Fixed => "fixed",
+                RedemptionMode::ProRata => "pro_rata",
+            },
+        ),
+    ]))
+}
+
+fn try_deposit_native(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+    if info.funds.is_empty() {
+        return Err(ContractError::Std(StdError::generic_err(
+            "No coins sent to deposit",
+        )));
+    }
+
+    let basket = load_basket(deps.as_ref())?;
+    let depositor = deps.api.addr_canonicalize(info.sender.as_str())?;
+    for coin in info.funds.iter() {
+        let denom_info = AssetInfo::NativeToken {
+            denom: coin.denom.to_string(),
+        };
+        if !basket.iter().any(|asset| asset.info.equal(&denom_info)) {
+            return Err(ContractError::Std(StdError::generic_err(format!(
+                "{} is not in the basket",
+                coin.denom
+            ))));
+        }
+        DEPOSITS.update(
+            deps.storage,
+            (depositor.as_slice(), coin.denom.as_str()),
+            |deposit| -> StdResult<_> { Ok(deposit.unwrap_or_default() + coin.amount) },
+        )?;
+    }
+
+    let amount = info
+        .funds
+        .iter()
+        .map(|coin| coin.to_string())
+        .collect::<Vec<String>>()
+        .join(",");
+    Ok(Response::new().add_attributes(vec![
+        ("action", "deposit_native"),
+        ("depositor", info.sender.as_str()),
+        ("amount", amount.as_str()),
+    ]))
+}
+
+fn try_withdraw_deposits(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+    let depositor = deps.api.addr_canonicalize(info.sender.as_str())?;
+    let deposits = load_deposits(deps.storage, &depositor)?;
+    if deposits.is_empty() {
+        return Err(ContractError::Std(StdError::generic_err(
+            "No deposits to withdraw",
+        )));
+    }
+
+    let mut messages: Vec<CosmosMsg> = vec![];
+    for coin in deposits.iter() {
+        DEPOSITS.remove(deps.storage, (depositor.as_slice(), coin.denom.as_str()));
+        let asset = Asset {
+            info: AssetInfo::NativeToken {
+                denom: coin.denom.to_string(),
+            },
+            amount: coin.amount,
+        };
+        messages.push(asset.into_msg(&deps.querier, info.sender.clone())?);
+    }
+
+    let amount = deposits
+        .iter()
+        .map(|coin| coin.to_string())
+        .collect::<Vec<String>>()
+        .join(",");
+    Ok(Response::new().add_messages(messages).add_attributes(vec![
+        ("action", "withdraw_deposits"),
+        ("depositor", info.sender.as_str()),
+        ("amount", amount.as_str()),
+    ]))
+}
+
+pub fn query_deposits(deps: Deps, address: String) -> StdResult<DepositsResponse> {
+    let depositor = deps.api.addr_canonicalize(&address)?;
+    Ok(DepositsResponse {
+        deposits: load_deposits(deps.storage, &depositor)?,
+    })
+}
+
+pub fn query_redemption_preview(
+    deps: Deps,
+    env: Env,
+    amount: Uint128,
+) -> StdResult<RedemptionPreviewResponse> {
+    let config = CONFIGURATION.load(deps.storage)?;
+    if amount > TOKEN_INFO.load(deps.storage)?.total_supply {
+        return Err(StdError::generic_err(
+            "Cannot redeem more than the total supply",
+        ));
+    }
+
+    let assets = redemption_assets(deps, &env, &config, amount)?;
+    Ok(RedemptionPreviewResponse {
+        mode: config.redemption_mode,
+        assets,
+    })
+}
+
+pub fn query_backing(deps: Deps, env: Env) -> StdResult<BackingResponse> {
+    let basket = load_basket(deps)?;
+    let total_supply = TOKEN_INFO.load(deps.storage)?.total_supply;
+
+    let mut assets: Vec<AssetBackingResponse> = vec![];
+    for asset in basket {
+        let balance = query_reserve(deps, &env, &asset.info)?;
+        assets.push(asset_backing(asset, balance, total_supply));
+    }
+
+    let collateral_ratio = assets
+        .iter()
+        .filter(|asset| !asset.required.is_zero())
+        .map(|asset| Decimal::from_ratio(asset.balance, asset.required))
+        .min();
+    let fully_backed = assets.iter().all(|asset| asset.shortfall.is_zero());
+
+    Ok(BackingResponse {
+        total_supply,
+        assets,
+        collateral_ratio,
+        fully_backed,
+    })
+}
+
+fn asset_backing(
+    asset: BasketAsset,
+    balance: Uint128,
+    total_supply: Uint128,
+) -> AssetBackingResponse {
+    let required = total_supply * asset.rate;
+    AssetBackingResponse {
+        info: asset.info,
+        rate: asset.rate,
+        balance,
+        required,
+        shortfall: required.saturating_sub(balance),
     }
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> StdResult<Response> {
+pub fn migrate(deps: DepsMut, _env: Env, msg: MigrateMsg) -> StdResult<Response> {
+    let contract_version = get_contract_version(deps.storage)?;
+    if contract_version.contract != CONTRACT_NAME {
+        return Err(StdError::generic_err(format!(
+            "cannot migrate from contract {}",
+            contract_version.contract
+        )));
+    }
+    if parse_version(&contract_version.version)? > parse_version(CONTRACT_VERSION)? {
+        return Err(StdError::generic_err(format!(
+            "cannot migrate from newer version {}",
+            contract_version.version
+        )));
+    }
+
+    run_migrations(deps.storage, &contract_version.version)?;
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    if let Some(admin) = msg.admin {
+        let admin = deps.api.addr_canonicalize(&admin)?;
+        CONFIGURATION.update(deps.storage, |mut config| -> StdResult<_> {
+            config.admin = Some(admin);
+            Ok(config)
+        })?;
+    }
     Ok(Response::default())
 }
@@ -0,0 +1,77 @@
+use cosmwasm_std::{CanonicalAddr, Decimal, StdError, StdResult, Storage};
+use cw_storage_plus::Item;
+use gohm_staking::asset::AssetInfoRaw;
+use gohm_staking::reward_token::RedemptionMode;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::state::{BasketAssetRaw, Config, CONFIGURATION};
+
+type MigrationStep = fn(&mut dyn Storage) -> StdResult<()>;
+
+/// Storage migrations in order, with the version whose layout they produce.
+/// A deployment runs every step newer than its stored version; layouts that
+/// never shipped in a release get no step of their own
+const MIGRATIONS: &[(&str, MigrationStep)] = &[("0.1.0", migrate_basket_config)];
+
+pub fn run_migrations(storage: &mut dyn Storage, from_version: &str) -> StdResult<()> {
+    let from_version = parse_version(from_version)?;
+    for (version, step) in MIGRATIONS.iter() {
+        if parse_version(version)? > from_version {
+            step(storage)?;
+        }
+    }
+    Ok(())
+}
+
+/// splits a `major.minor.patch` version for comparison
+pub fn parse_version(version: &str) -> StdResult<(u64, u64, u64)> {
+    let invalid_version = || StdError::generic_err(format!("invalid version {}", version));
+    let parts = version
+        .split('.')
+        .map(|part| part.parse::<u64>())
+        .collect::<Result<Vec<u64>, _>>()
+        .map_err(|_| invalid_version())?;
+    match parts[..] {
+        [major, minor, patch] => Ok((major, minor, patch)),
+        _ => Err(invalid_version()),
+    }
+}
+
+/// Config of the single gOHM + native denom token
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct LegacyConfig {
+    pub gohm_token: CanonicalAddr,
+    pub denom: String,
+    pub gohm_rate: Decimal,
+    pub denom_rate: Decimal,
+}
+
+pub const LEGACY_CONFIGURATION: Item<LegacyConfig> = Item::new("config");
+
+/// Turn the gOHM + denom config into a basket; the native denom goes
+/// first so burns keep paying out in the same order
+fn migrate_basket_config(storage: &mut dyn Storage) -> StdResult<()> {
+    let legacy_config = LEGACY_CONFIGURATION.load(storage)?;
+    CONFIGURATION.save(
+        storage,
+        &Config {
+            basket: vec![
+                BasketAssetRaw {
+                    info: AssetInfoRaw::NativeToken {
+                        denom: legacy_config.denom,
+                    },
+                    rate: legacy_config.denom_rate,
+                },
+                BasketAssetRaw {
+                    info: AssetInfoRaw::Token {
+                        contract_addr: legacy_config.gohm_token,
+                    },
+                    rate: legacy_config.gohm_rate,
+                },
+            ],
+            admin: None,
+            redemption_mode: RedemptionMode::Fixed,
+        },
+    )
+}
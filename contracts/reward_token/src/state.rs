@@ -1,15 +1,41 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use cosmwasm_std::{CanonicalAddr, Decimal};
-use cw_storage_plus::Item;
+use cosmwasm_std::{Api, CanonicalAddr, Decimal, StdResult, Uint128};
+use cw_storage_plus::{Item, Map};
+use gohm_staking::asset::AssetInfoRaw;
+use gohm_staking::reward_token::{BasketAsset, RedemptionMode};
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct Config {
-    pub gohm_token: CanonicalAddr,
-    pub denom: String,
-    pub gohm_rate: Decimal,
-    pub denom_rate: Decimal,
+    pub basket: Vec<BasketAssetRaw>,
+    pub admin: Option<CanonicalAddr>,
+    pub redemption_mode: RedemptionMode,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BasketAssetRaw {
+    pub info: AssetInfoRaw,
+    pub rate: Decimal,
+}
+
+impl BasketAssetRaw {
+    pub fn from_normal(api: &dyn Api, asset: &BasketAsset) -> StdResult<Self> {
+        Ok(BasketAssetRaw {
+            info: asset.info.to_raw(api)?,
+            rate: asset.rate,
+        })
+    }
+
+    pub fn to_normal(&self, api: &dyn Api) -> StdResult<BasketAsset> {
+        Ok(BasketAsset {
+            info: self.info.to_normal(api)?,
+            rate: self.rate,
+        })
+    }
 }
 
 pub const CONFIGURATION: Item<Config> = Item::new("config");
+
+/// Native coins waiting for a MintHook, keyed by (depositor, denom)
+pub const DEPOSITS: Map<(&[u8], &str), Uint128> = Map::new("deposits");
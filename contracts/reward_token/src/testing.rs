@@ -1,17 +1,20 @@
-use crate::contract::{execute, instantiate, query};
-use cosmwasm_std::testing::{
-    mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage, MOCK_CONTRACT_ADDR,
-};
+use crate::contract::{execute, instantiate, migrate, query};
+use crate::migration::{LegacyConfig, LEGACY_CONFIGURATION};
+use crate::mock_querier::{mock_dependencies, WasmMockQuerier};
+use crate::state::CONFIGURATION;
+use cosmwasm_std::testing::{mock_env, mock_info, MockApi, MockStorage, MOCK_CONTRACT_ADDR};
 use cosmwasm_std::{
-    from_binary, to_binary, BankMsg, Coin, CosmosMsg, Decimal, OwnedDeps, Response, StdError,
+    from_binary, to_binary, Api, BankMsg, Coin, CosmosMsg, Decimal, OwnedDeps, Response, StdError,
     SubMsg, Uint128, WasmMsg,
 };
-use cw20::{BalanceResponse, MinterResponse, TokenInfoResponse};
-use cw20_legacy::{
-    msg::{ExecuteMsg, QueryMsg},
-    ContractError,
+use cw2::{get_contract_version, set_contract_version};
+use cw20::{BalanceResponse, Cw20ReceiveMsg, MinterResponse, TokenInfoResponse};
+use cw20_legacy::ContractError;
+use gohm_staking::asset::{Asset, AssetInfo};
+use gohm_staking::reward_token::{
+    AssetBackingResponse, BackingResponse, BasketAsset, Cw20HookMsg, DepositsResponse, ExecuteMsg,
+    InstantiateMsg, MigrateMsg, QueryMsg, RedemptionMode, RedemptionPreviewResponse,
 };
-use gohm_staking::reward_token::InstantiateMsg;
 
 #[test]
 fn proper_initialization() {
@@ -22,10 +25,8 @@ fn proper_initialization() {
         symbol: "rgOHM".to_string(),
         decimals: 6u8,
         minter: "minter".to_string(),
-        gohm_token: "gohm_token".to_string(),
-        denom: "uluna".to_string(),
-        gohm_rate: Decimal::percent(1000),
-        denom_rate: Decimal::percent(10),
+        basket: basket(Decimal::percent(1000), Decimal::percent(10)),
+        admin: None,
     };
 
     let info = mock_info("addr0000", &[]);
@@ -83,7 +84,7 @@ fn test_mint_tokens_fails_if_unauthorized() {
 }
 
 #[test]
-fn test_mint_tokens_fails_if_several_denoms_received() {
+fn test_mint_tokens_fails_if_denom_not_in_basket() {
     let mut deps = mock_dependencies(&[]);
 
     let (_, denom_rate) = initialize_reward_token(&mut deps, None, None);
@@ -112,7 +113,7 @@ fn test_mint_tokens_fails_if_several_denoms_received() {
 
     assert_eq!(
         res,
-        ContractError::Std(StdError::generic_err("Cannot receive several denoms",))
+        ContractError::Std(StdError::generic_err("uust is not in the basket"))
     );
 }
 
@@ -296,7 +297,7 @@ fn test_mint_tokens_when_denom_rate_is_zero() {
 
 // helper
 fn initialize_reward_token(
-    deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>,
+    deps: &mut OwnedDeps<MockStorage, MockApi, WasmMockQuerier>,
     gohm_rate: Option<Decimal>,
     denom_rate: Option<Decimal>,
 ) -> (Decimal, Decimal) {
@@ -317,10 +318,8 @@ fn initialize_reward_token(
         symbol: "rgOHM".to_string(),
         decimals: 6u8,
         minter: "minter".to_string(),
-        gohm_token: "gohm_token".to_string(),
-        denom: "uluna".to_string(),
-        gohm_rate,
-        denom_rate,
+        basket: basket(gohm_rate, denom_rate),
+        admin: Some("admin".to_string()),
     };
 
     let info = mock_info("addr", &[]);
@@ -330,6 +329,24 @@ fn initialize_reward_token(
     (gohm_rate, denom_rate)
 }
 
+// uluna and gOHM basket
+fn basket(gohm_rate: Decimal, denom_rate: Decimal) -> Vec<BasketAsset> {
+    vec![
+        BasketAsset {
+            info: AssetInfo::NativeToken {
+                denom: "uluna".to_string(),
+            },
+            rate: denom_rate,
+        },
+        BasketAsset {
+            info: AssetInfo::Token {
+                contract_addr: "gohm_token".to_string(),
+            },
+            rate: gohm_rate,
+        },
+    ]
+}
+
 #[test]
 fn test_burn_tokens_fails_if_no_balance() {
     let mut deps = mock_dependencies(&[]);
@@ -361,13 +378,14 @@ fn test_burn_tokens_when_all_rates_are_not_zero() {
     let (gohm_rate, denom_rate) = initialize_reward_token(&mut deps, None, None);
 
     let mint_amount = Uint128::from(1000000u128);
-    mint_token(
+    let (_, gohm_amount, denom_amount) = mint_token(
         &mut deps,
         gohm_rate,
         denom_rate,
         mint_amount,
         "recipient".to_string(),
     );
+    set_reserves(&mut deps, gohm_amount, denom_amount);
 
     let amount = Uint128::from(1000000u128);
 
@@ -431,13 +449,14 @@ fn test_burn_tokens_when_gohm_rate_is_zero() {
     let (gohm_rate, denom_rate) = initialize_reward_token(&mut deps, Some(Decimal::zero()), None);
 
     let mint_amount = Uint128::from(1000000u128);
-    mint_token(
+    let (_, gohm_amount, denom_amount) = mint_token(
         &mut deps,
         gohm_rate,
         denom_rate,
         mint_amount,
         "recipient".to_string(),
     );
+    set_reserves(&mut deps, gohm_amount, denom_amount);
 
     let amount = Uint128::from(1000000u128);
 
@@ -490,13 +509,14 @@ fn test_burn_tokens_when_denom_rate_is_zero() {
     let (gohm_rate, denom_rate) = initialize_reward_token(&mut deps, None, Some(Decimal::zero()));
 
     let mint_amount = Uint128::from(1000000u128);
-    mint_token(
+    let (_, gohm_amount, denom_amount) = mint_token(
         &mut deps,
         gohm_rate,
         denom_rate,
         mint_amount,
         "recipient".to_string(),
     );
+    set_reserves(&mut deps, gohm_amount, denom_amount);
 
     let amount = Uint128::from(1000000u128);
 
@@ -545,7 +565,7 @@ fn test_burn_tokens_when_denom_rate_is_zero() {
 }
 
 fn mint_token(
-    deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>,
+    deps: &mut OwnedDeps<MockStorage, MockApi, WasmMockQuerier>,
     gohm_rate: Decimal,
     denom_rate: Decimal,
     amount: Uint128,
@@ -566,8 +586,27 @@ fn mint_token(
     (res, amount * gohm_rate, amount * denom_rate)
 }
 
+// fund the contract as if the mints had transferred the backing assets
+fn set_reserves(
+    deps: &mut OwnedDeps<MockStorage, MockApi, WasmMockQuerier>,
+    gohm_amount: Uint128,
+    denom_amount: Uint128,
+) {
+    deps.querier.with_token_balances(&[(
+        &"gohm_token".to_string(),
+        &[(&MOCK_CONTRACT_ADDR.to_string(), &gohm_amount)],
+    )]);
+    deps.querier.with_balance(&[(
+        &MOCK_CONTRACT_ADDR.to_string(),
+        &[Coin {
+            denom: "uluna".to_string(),
+            amount: denom_amount,
+        }],
+    )]);
+}
+
 fn burn_token(
-    deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>,
+    deps: &mut OwnedDeps<MockStorage, MockApi, WasmMockQuerier>,
     amount: Uint128,
     sender: String,
 ) -> Response {
@@ -577,3 +616,732 @@ fn burn_token(
 
     execute(deps.as_mut(), mock_env(), info, msg).unwrap()
 }
+
+#[test]
+fn test_query_backing() {
+    let mut deps = mock_dependencies(&[Coin {
+        denom: "uluna".to_string(),
+        amount: Uint128::from(100000u128),
+    }]);
+    deps.querier.with_token_balances(&[(
+        &"gohm_token".to_string(),
+        &[(
+            &MOCK_CONTRACT_ADDR.to_string(),
+            &Uint128::from(12000000u128),
+        )],
+    )]);
+
+    let (gohm_rate, denom_rate) = initialize_reward_token(&mut deps, None, None);
+
+    // nothing is required before minting
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Backing {}).unwrap();
+    let backing: BackingResponse = from_binary(&res).unwrap();
+    assert_eq!(backing.collateral_ratio, None);
+    assert!(backing.fully_backed);
+
+    let amount = Uint128::from(1000000u128);
+    mint_token(
+        &mut deps,
+        gohm_rate,
+        denom_rate,
+        amount,
+        "recipient".to_string(),
+    );
+
+    // 10000000 gOHM and 100000 uluna are required
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Backing {}).unwrap();
+    let backing: BackingResponse = from_binary(&res).unwrap();
+    assert_eq!(
+        backing,
+        BackingResponse {
+            total_supply: amount,
+            assets: vec![
+                AssetBackingResponse {
+                    info: AssetInfo::NativeToken {
+                        denom: "uluna".to_string(),
+                    },
+                    rate: denom_rate,
+                    balance: Uint128::from(100000u128),
+                    required: Uint128::from(100000u128),
+                    shortfall: Uint128::zero(),
+                },
+                AssetBackingResponse {
+                    info: AssetInfo::Token {
+                        contract_addr: "gohm_token".to_string(),
+                    },
+                    rate: gohm_rate,
+                    balance: Uint128::from(12000000u128),
+                    required: Uint128::from(10000000u128),
+                    shortfall: Uint128::zero(),
+                },
+            ],
+            collateral_ratio: Some(Decimal::one()),
+            fully_backed: true,
+        }
+    );
+
+    // a gOHM shortfall lowers the collateral ratio
+    deps.querier.with_token_balances(&[(
+        &"gohm_token".to_string(),
+        &[(&MOCK_CONTRACT_ADDR.to_string(), &Uint128::from(8000000u128))],
+    )]);
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Backing {}).unwrap();
+    let backing: BackingResponse = from_binary(&res).unwrap();
+    assert_eq!(backing.assets[1].shortfall, Uint128::from(2000000u128));
+    assert_eq!(backing.collateral_ratio, Some(Decimal::percent(80)));
+    assert!(!backing.fully_backed);
+}
+
+#[test]
+fn test_update_rates() {
+    let mut deps = mock_dependencies(&[]);
+
+    let (gohm_rate, denom_rate) = initialize_reward_token(&mut deps, None, None);
+
+    let amount = Uint128::from(1000000u128);
+    let (_, gohm_amount, denom_amount) = mint_token(
+        &mut deps,
+        gohm_rate,
+        denom_rate,
+        amount,
+        "recipient".to_string(),
+    );
+    set_reserves(&mut deps, gohm_amount, denom_amount);
+
+    let msg = ExecuteMsg::UpdateRates {
+        basket: vec![BasketAsset {
+            info: AssetInfo::Token {
+                contract_addr: "gohm_token".to_string(),
+            },
+            rate: Decimal::percent(2000),
+        }],
+    };
+    let info = mock_info("minter", &[]);
+    let res = execute(deps.as_mut(), mock_env(), info, msg.clone()).unwrap_err();
+    assert_eq!(res, ContractError::Unauthorized {});
+
+    let info = mock_info("admin", &[]);
+    let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    // the old supply only gets its share of the reserves
+    let res = burn_token(
+        &mut deps,
+        Uint128::from(500000u128),
+        "recipient".to_string(),
+    );
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+                to_address: "recipient".to_string(),
+                amount: vec![Coin {
+                    denom: "uluna".to_string(),
+                    amount: Uint128::from(50000u128),
+                }],
+            })),
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "gohm_token".to_string(),
+                msg: to_binary(&ExecuteMsg::Transfer {
+                    recipient: "recipient".to_string(),
+                    amount: Uint128::from(5000000u128),
+                })
+                .unwrap(),
+                funds: vec![],
+            })),
+        ]
+    );
+
+    // new mints pull in the new rate
+    let (res, gohm_amount, _) = mint_token(
+        &mut deps,
+        Decimal::percent(2000),
+        denom_rate,
+        amount,
+        "recipient".to_string(),
+    );
+    assert_eq!(gohm_amount, Uint128::from(20000000u128));
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "gohm_token".to_string(),
+            msg: to_binary(&ExecuteMsg::TransferFrom {
+                owner: "minter".to_string(),
+                recipient: MOCK_CONTRACT_ADDR.to_string(),
+                amount: gohm_amount,
+            })
+            .unwrap(),
+            funds: vec![],
+        }))]
+    );
+}
+
+// property test: random mints, burns, rate updates and reserve losses
+// never make a burn pay out more than the reserves held
+#[test]
+fn test_burns_stay_solvent() {
+    let mut deps = mock_dependencies(&[]);
+
+    let (mut gohm_rate, mut denom_rate) = initialize_reward_token(&mut deps, None, None);
+    let holders = ["holder0", "holder1", "holder2"];
+    let mut gohm_reserve = Uint128::zero();
+    let mut denom_reserve = Uint128::zero();
+
+    // xorshift64, seeded so failures reproduce
+    let mut seed = 0x2545f4914f6cdd1du64;
+    let mut next = |bound: u64| {
+        seed ^= seed << 13;
+        seed ^= seed >> 7;
+        seed ^= seed << 17;
+        seed % bound
+    };
+
+    for _ in 0..1000 {
+        let holder = holders[next(3) as usize];
+        match next(10) {
+            0..=3 => {
+                let amount = Uint128::from(next(1000000) + 1);
+                let (_, gohm_amount, denom_amount) =
+                    mint_token(&mut deps, gohm_rate, denom_rate, amount, holder.to_string());
+                gohm_reserve += gohm_amount;
+                denom_reserve += denom_amount;
+            }
+            4..=7 => {
+                let balance = query_balance(&deps, holder);
+                if balance.is_zero() {
+                    continue;
+                }
+                let amount = Uint128::from(next(balance.u128() as u64) + 1);
+                let res = burn_token(&mut deps, amount, holder.to_string());
+                for msg in res.messages {
+                    match msg.msg {
+                        CosmosMsg::Bank(BankMsg::Send { amount, .. }) => {
+                            denom_reserve = denom_reserve.checked_sub(amount[0].amount).unwrap();
+                        }
+                        CosmosMsg::Wasm(WasmMsg::Execute { msg, .. }) => {
+                            match from_binary(&msg).unwrap() {
+                                ExecuteMsg::Transfer { amount, .. } => {
+                                    gohm_reserve = gohm_reserve.checked_sub(amount).unwrap();
+                                }
+                                _ => panic!("unexpected burn message"),
+                            }
+                        }
+                        _ => panic!("unexpected burn message"),
+                    }
+                }
+            }
+            8 => {
+                gohm_rate = Decimal::percent(next(2000));
+                denom_rate = Decimal::percent(next(20));
+                let msg = ExecuteMsg::UpdateRates {
+                    basket: basket(gohm_rate, denom_rate),
+                };
+                let info = mock_info("admin", &[]);
+                execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+            }
+            _ => {
+                // reserves lost to tax or rounding
+                gohm_reserve = gohm_reserve.multiply_ratio(next(100), 100u128);
+                denom_reserve = denom_reserve.multiply_ratio(next(100), 100u128);
+            }
+        }
+        set_reserves(&mut deps, gohm_reserve, denom_reserve);
+    }
+
+    // everyone can still burn everything
+    for holder in holders.iter() {
+        let balance = query_balance(&deps, holder);
+        if !balance.is_zero() {
+            burn_token(&mut deps, balance, holder.to_string());
+        }
+    }
+}
+
+#[test]
+fn test_pro_rata_redemption() {
+    let mut deps = mock_dependencies(&[]);
+
+    let (gohm_rate, denom_rate) = initialize_reward_token(&mut deps, None, None);
+
+    let amount = Uint128::from(1000000u128);
+    mint_token(
+        &mut deps,
+        gohm_rate,
+        denom_rate,
+        amount,
+        "recipient".to_string(),
+    );
+    // gOHM is over-backed while half of the uluna is missing
+    set_reserves(
+        &mut deps,
+        Uint128::from(12000000u128),
+        Uint128::from(50000u128),
+    );
+
+    let preview = |deps: &OwnedDeps<MockStorage, MockApi, WasmMockQuerier>| {
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::RedemptionPreview {
+                amount: Uint128::from(500000u128),
+            },
+        )
+        .unwrap();
+        let preview: RedemptionPreviewResponse = from_binary(&res).unwrap();
+        preview
+    };
+    let redemption = |denom_amount: u128, gohm_amount: u128| {
+        vec![
+            Asset {
+                info: AssetInfo::NativeToken {
+                    denom: "uluna".to_string(),
+                },
+                amount: Uint128::from(denom_amount),
+            },
+            Asset {
+                info: AssetInfo::Token {
+                    contract_addr: "gohm_token".to_string(),
+                },
+                amount: Uint128::from(gohm_amount),
+            },
+        ]
+    };
+
+    // fixed rates, capped at the burned share of the reserves
+    assert_eq!(
+        preview(&deps),
+        RedemptionPreviewResponse {
+            mode: RedemptionMode::Fixed,
+            assets: redemption(25000, 5000000),
+        }
+    );
+
+    let msg = ExecuteMsg::SetRedemptionMode {
+        mode: RedemptionMode::ProRata,
+    };
+    let info = mock_info("minter", &[]);
+    let res = execute(deps.as_mut(), mock_env(), info, msg.clone()).unwrap_err();
+    assert_eq!(res, ContractError::Unauthorized {});
+
+    let info = mock_info("admin", &[]);
+    let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    // half of the supply gets half of every reserve
+    assert_eq!(
+        preview(&deps),
+        RedemptionPreviewResponse {
+            mode: RedemptionMode::ProRata,
+            assets: redemption(25000, 6000000),
+        }
+    );
+
+    let res = burn_token(
+        &mut deps,
+        Uint128::from(500000u128),
+        "recipient".to_string(),
+    );
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+                to_address: "recipient".to_string(),
+                amount: vec![Coin {
+                    denom: "uluna".to_string(),
+                    amount: Uint128::from(25000u128),
+                }],
+            })),
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "gohm_token".to_string(),
+                msg: to_binary(&ExecuteMsg::Transfer {
+                    recipient: "recipient".to_string(),
+                    amount: Uint128::from(6000000u128),
+                })
+                .unwrap(),
+                funds: vec![],
+            })),
+        ]
+    );
+
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::RedemptionPreview {
+            amount: Uint128::from(500001u128),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        res,
+        StdError::generic_err("Cannot redeem more than the total supply")
+    );
+}
+
+#[test]
+fn test_mint_and_burn_basket() {
+    let mut deps = mock_dependencies(&[]);
+
+    let mut basket = basket(Decimal::percent(1000), Decimal::percent(10));
+    basket.push(BasketAsset {
+        info: AssetInfo::Token {
+            contract_addr: "token0000".to_string(),
+        },
+        rate: Decimal::percent(200),
+    });
+    let msg = InstantiateMsg {
+        name: "gOHM reward token".to_string(),
+        symbol: "rgOHM".to_string(),
+        decimals: 6u8,
+        minter: "minter".to_string(),
+        basket: basket.clone(),
+        admin: None,
+    };
+    let info = mock_info("addr", &[]);
+    instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    // a basket asset cannot be listed twice
+    let msg = InstantiateMsg {
+        name: "gOHM reward token".to_string(),
+        symbol: "rgOHM".to_string(),
+        decimals: 6u8,
+        minter: "minter".to_string(),
+        basket: vec![basket[1].clone(), basket[1].clone()],
+        admin: None,
+    };
+    let info = mock_info("addr", &[]);
+    let res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+    assert_eq!(res, StdError::generic_err("Duplicate basket asset"));
+
+    // every cw20 in the basket is pulled in
+    let amount = Uint128::from(1000000u128);
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(
+            "minter",
+            &[Coin {
+                denom: "uluna".to_string(),
+                amount: Uint128::from(100000u128),
+            }],
+        ),
+        ExecuteMsg::Mint {
+            recipient: "recipient".to_string(),
+            amount,
+        },
+    )
+    .unwrap();
+    let transfer_from = |token: &str, amount: u128| {
+        SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: token.to_string(),
+            msg: to_binary(&ExecuteMsg::TransferFrom {
+                owner: "minter".to_string(),
+                recipient: MOCK_CONTRACT_ADDR.to_string(),
+                amount: Uint128::from(amount),
+            })
+            .unwrap(),
+            funds: vec![],
+        }))
+    };
+    assert_eq!(
+        res.messages,
+        vec![
+            transfer_from("gohm_token", 10000000),
+            transfer_from("token0000", 2000000),
+        ]
+    );
+
+    deps.querier.with_token_balances(&[
+        (
+            &"gohm_token".to_string(),
+            &[(
+                &MOCK_CONTRACT_ADDR.to_string(),
+                &Uint128::from(10000000u128),
+            )],
+        ),
+        (
+            &"token0000".to_string(),
+            &[(&MOCK_CONTRACT_ADDR.to_string(), &Uint128::from(2000000u128))],
+        ),
+    ]);
+    deps.querier.with_balance(&[(
+        &MOCK_CONTRACT_ADDR.to_string(),
+        &[Coin {
+            denom: "uluna".to_string(),
+            amount: Uint128::from(100000u128),
+        }],
+    )]);
+
+    // and every basket asset is paid out
+    let res = burn_token(
+        &mut deps,
+        Uint128::from(500000u128),
+        "recipient".to_string(),
+    );
+    let transfer = |token: &str, amount: u128| {
+        SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: token.to_string(),
+            msg: to_binary(&ExecuteMsg::Transfer {
+                recipient: "recipient".to_string(),
+                amount: Uint128::from(amount),
+            })
+            .unwrap(),
+            funds: vec![],
+        }))
+    };
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+                to_address: "recipient".to_string(),
+                amount: vec![Coin {
+                    denom: "uluna".to_string(),
+                    amount: Uint128::from(50000u128),
+                }],
+            })),
+            transfer("gohm_token", 5000000),
+            transfer("token0000", 1000000),
+        ]
+    );
+}
+
+#[test]
+fn test_migrate_basket_config() {
+    let mut deps = mock_dependencies(&[]);
+    initialize_reward_token(&mut deps, None, None);
+
+    // a gOHM + denom token as first released
+    set_contract_version(&mut deps.storage, "crates.io:cw20-base", "0.0.1").unwrap();
+    LEGACY_CONFIGURATION
+        .save(
+            &mut deps.storage,
+            &LegacyConfig {
+                gohm_token: deps.api.addr_canonicalize("gohm_token").unwrap(),
+                denom: "uluna".to_string(),
+                gohm_rate: Decimal::percent(1000),
+                denom_rate: Decimal::percent(10),
+            },
+        )
+        .unwrap();
+
+    let msg = MigrateMsg {
+        admin: Some("admin".to_string()),
+    };
+    let _res = migrate(deps.as_mut(), mock_env(), msg).unwrap();
+
+    let config = CONFIGURATION.load(deps.as_ref().storage).unwrap();
+    assert_eq!(
+        config
+            .basket
+            .iter()
+            .map(|asset| asset.to_normal(deps.as_ref().api).unwrap())
+            .collect::<Vec<BasketAsset>>(),
+        basket(Decimal::percent(1000), Decimal::percent(10))
+    );
+    assert_eq!(
+        config.admin,
+        Some(deps.api.addr_canonicalize("admin").unwrap())
+    );
+    assert_eq!(config.redemption_mode, RedemptionMode::Fixed);
+    assert_eq!(
+        get_contract_version(&deps.storage).unwrap().version,
+        env!("CARGO_PKG_VERSION")
+    );
+
+    // the stored version now skips the conversion
+    let msg = MigrateMsg { admin: None };
+    let _res = migrate(deps.as_mut(), mock_env(), msg).unwrap();
+    assert_eq!(CONFIGURATION.load(deps.as_ref().storage).unwrap(), config);
+}
+
+#[test]
+fn test_mint_hook() {
+    let mut deps = mock_dependencies(&[]);
+
+    let (gohm_rate, denom_rate) = initialize_reward_token(&mut deps, None, None);
+
+    let amount = Uint128::from(1000000u128);
+    let mint_hook = |sender: &str, gohm_amount: Uint128| {
+        ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: sender.to_string(),
+            amount: gohm_amount,
+            msg: to_binary(&Cw20HookMsg::MintHook {
+                recipient: "recipient".to_string(),
+            })
+            .unwrap(),
+        })
+    };
+
+    // only basket cw20s can mint
+    let info = mock_info("token0000", &[]);
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        mint_hook("minter", amount * gohm_rate),
+    )
+    .unwrap_err();
+    assert_eq!(
+        res,
+        ContractError::Std(StdError::generic_err("token0000 is not in the basket"))
+    );
+
+    // the uluna part of the basket has to be deposited first
+    let info = mock_info("gohm_token", &[]);
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        mint_hook("minter", amount * gohm_rate),
+    )
+    .unwrap_err();
+    assert_eq!(
+        res,
+        ContractError::Std(StdError::generic_err("Insufficient uluna deposit"))
+    );
+
+    // only native basket assets can be deposited
+    let info = mock_info(
+        "minter",
+        &[Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::from(100u128),
+        }],
+    );
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        ExecuteMsg::DepositNative {},
+    )
+    .unwrap_err();
+    assert_eq!(
+        res,
+        ContractError::Std(StdError::generic_err("uusd is not in the basket"))
+    );
+
+    // the cw20 sender must be the minter
+    let info = mock_info(
+        "addr0000",
+        &[Coin {
+            denom: "uluna".to_string(),
+            amount: amount * denom_rate,
+        }],
+    );
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        ExecuteMsg::DepositNative {},
+    )
+    .unwrap();
+    let info = mock_info("gohm_token", &[]);
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        mint_hook("addr0000", amount * gohm_rate),
+    )
+    .unwrap_err();
+    assert_eq!(res, ContractError::Unauthorized {});
+
+    let info = mock_info(
+        "minter",
+        &[Coin {
+            denom: "uluna".to_string(),
+            amount: amount * denom_rate + Uint128::from(500u128),
+        }],
+    );
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        ExecuteMsg::DepositNative {},
+    )
+    .unwrap();
+
+    // gOHM that doesn't make up a whole reward token goes back to the minter
+    let info = mock_info("gohm_token", &[]);
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        mint_hook("minter", amount * gohm_rate + Uint128::from(5u128)),
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "gohm_token".to_string(),
+            msg: to_binary(&ExecuteMsg::Transfer {
+                recipient: "minter".to_string(),
+                amount: Uint128::from(5u128),
+            })
+            .unwrap(),
+            funds: vec![],
+        }))]
+    );
+    assert_eq!(query_balance(&deps, "recipient"), amount);
+
+    // only the uluna for the minted amount was used
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::Deposits {
+            address: "minter".to_string(),
+        },
+    )
+    .unwrap();
+    let deposits: DepositsResponse = from_binary(&res).unwrap();
+    assert_eq!(
+        deposits.deposits,
+        vec![Coin {
+            denom: "uluna".to_string(),
+            amount: Uint128::from(500u128),
+        }]
+    );
+
+    let info = mock_info("minter", &[]);
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        info.clone(),
+        ExecuteMsg::WithdrawDeposits {},
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+            to_address: "minter".to_string(),
+            amount: vec![Coin {
+                denom: "uluna".to_string(),
+                amount: Uint128::from(500u128),
+            }],
+        }))]
+    );
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        ExecuteMsg::WithdrawDeposits {},
+    )
+    .unwrap_err();
+    assert_eq!(
+        res,
+        ContractError::Std(StdError::generic_err("No deposits to withdraw"))
+    );
+}
+
+fn query_balance(
+    deps: &OwnedDeps<MockStorage, MockApi, WasmMockQuerier>,
+    address: &str,
+) -> Uint128 {
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::Balance {
+            address: address.to_string(),
+        },
+    )
+    .unwrap();
+    let balance: BalanceResponse = from_binary(&res).unwrap();
+    balance.balance
+}
@@ -3,8 +3,9 @@ use std::fs::create_dir_all;
 
 use cosmwasm_schema::{export_schema, remove_schemas, schema_for};
 use gohm_staking::staking::{
-    ConfigResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, QueryMsg, StakerInfoResponse,
-    StateResponse,
+    AllStakersResponse, ConfigResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg,
+    PauseStatusResponse, PendingGovernanceResponse, QueryMsg, RewardRateResponse, SolvencyResponse,
+    StakerInfoResponse, StateResponse, UnbondingsResponse, WithdrawPermissionsResponse,
 };
 
 fn main() {
@@ -20,4 +21,11 @@ fn main() {
     export_schema(&schema_for!(ConfigResponse), &out_dir);
     export_schema(&schema_for!(StakerInfoResponse), &out_dir);
     export_schema(&schema_for!(StateResponse), &out_dir);
+    export_schema(&schema_for!(UnbondingsResponse), &out_dir);
+    export_schema(&schema_for!(AllStakersResponse), &out_dir);
+    export_schema(&schema_for!(WithdrawPermissionsResponse), &out_dir);
+    export_schema(&schema_for!(PauseStatusResponse), &out_dir);
+    export_schema(&schema_for!(SolvencyResponse), &out_dir);
+    export_schema(&schema_for!(RewardRateResponse), &out_dir);
+    export_schema(&schema_for!(PendingGovernanceResponse), &out_dir);
 }
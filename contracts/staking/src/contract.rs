@@ -2,23 +2,49 @@
 use cosmwasm_std::entry_point;
 
 use cosmwasm_std::{
-    from_binary, to_binary, Addr, Binary, CanonicalAddr, CosmosMsg, Decimal, Deps, DepsMut, Env,
-    MessageInfo, Response, StdError, StdResult, Uint128, WasmMsg,
+    from_binary, to_binary, Addr, BankMsg, Binary, CanonicalAddr, Coin, CosmosMsg, Decimal, Deps,
+    DepsMut, Env, MessageInfo, Response, StdError, StdResult, Storage, Uint128, WasmMsg,
 };
 
+use cosmwasm_bignumber::{Decimal256, Uint256};
+use gohm_staking::querier::{deduct_tax, query_balance, query_token_balance};
+
 use gohm_staking::staking::{
-    ConfigResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg,
-    StakerInfoResponse, StateResponse,
+    AllStakersResponse, ConfigResponse, Cw20HookMsg, DistributionSchedule, ExecuteMsg,
+    FundedSlotResponse, InstantiateMsg, MigrateMsg, Operation, PauseStatusResponse,
+    PendingGovernanceResponse, QueryMsg, RewardAsset, RewardAssetRateResponse, RewardRateResponse,
+    RewardSolvencyResponse, ScheduleChange, ScheduleChangeResponse, SolvencyResponse,
+    StakerInfoResponse, StateResponse, UnbondingResponse, UnbondingsResponse,
+    WithdrawPermissionsResponse,
 };
 
+use crate::migration::{parse_version, run_migrations};
 use crate::state::{
-    read_config, read_staker_info, read_state, remove_staker_info, store_config, store_staker_info,
-    store_state, Config, StakerInfo, State,
+    read_config, read_pause_info, read_schedule_change, read_schedule_changes, read_staker_info,
+    read_staker_infos, read_state, read_unbondings, read_withdraw_permissions,
+    remove_schedule_change, remove_staker_info, remove_unbondings, store_config, store_pause_info,
+    store_schedule_change, store_staker_info, store_state, store_unbondings,
+    store_withdraw_permissions, Config, FundedSlot, QueuedScheduleChange, RewardAssetRaw,
+    StakerInfo, State, UnbondingInfo,
 };
 
+use cw2::{set_contract_version, CONTRACT};
 use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+use gohm_staking::asset::{AssetInfo, AssetInfoRaw};
 use std::collections::BTreeMap;
 
+// version info for migration info
+const CONTRACT_NAME: &str = "crates.io:staking";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+const SECONDS_PER_YEAR: u64 = 365 * 24 * 60 * 60;
+
+// queued schedule changes can only be executed this long after their eta
+const SCHEDULE_CHANGE_GRACE_PERIOD: u64 = 14 * 24 * 60 * 60;
+
+// bounds the slots anyone can fund for a single reward asset
+const MAX_FUNDED_SLOTS: usize = 20;
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
     deps: DepsMut,
@@ -26,24 +52,52 @@ pub fn instantiate(
     _info: MessageInfo,
     msg: InstantiateMsg,
 ) -> StdResult<Response> {
-    store_config(
-        deps.storage,
-        &Config {
-            reward_token: deps.api.addr_canonicalize(&msg.reward_token)?,
-            staking_token: deps.api.addr_canonicalize(&msg.staking_token)?,
-            distribution_schedule: msg.distribution_schedule,
-            governance: deps.api.addr_canonicalize(&msg.governance)?,
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    let rewards = msg
+        .rewards
+        .iter()
+        .map(|reward| RewardAssetRaw::from_normal(deps.api, reward))
+        .collect::<StdResult<Vec<RewardAssetRaw>>>()?;
+    assert_unique_reward_tokens(&rewards)?;
+    for reward in rewards.iter() {
+        assert_distribution_schedule(&reward.distribution_schedule)?;
+    }
+    assert_boost_curve(&msg.boost_curve)?;
+
+    let config = Config {
+        staking_token: deps.api.addr_canonicalize(&msg.staking_token)?,
+        rewards: rewards.clone(),
+        governance: deps.api.addr_canonicalize(&msg.governance)?,
+        pending_governance: None,
+        guardian: match msg.guardian {
+            Some(guardian) => Some(deps.api.addr_canonicalize(&guardian)?),
+            None => None,
         },
-    )?;
+        unbonding_period: msg.unbonding_period,
+        boost_curve: msg.boost_curve,
+        reject_underfunded_schedules: msg.reject_underfunded_schedules,
+        schedule_timelock: msg.schedule_timelock,
+        migrated_to: None,
+    };
+    let state = State {
+        last_distributed: env.block.time.seconds(),
+        total_bond_amount: Uint128::zero(),
+        total_weight: Uint128::zero(),
+        total_unbonding_amount: Uint128::zero(),
+        global_reward_indexes: vec![Decimal256::zero(); rewards.len()],
+        reward_dust: vec![Decimal256::zero(); rewards.len()],
+        unclaimed_rewards: vec![Uint128::zero(); rewards.len()],
+        share_price: Decimal256::one(),
+    };
 
-    store_state(
-        deps.storage,
-        &State {
-            last_distributed: env.block.time.seconds(),
-            total_bond_amount: Uint128::zero(),
-            global_reward_index: Decimal::zero(),
-        },
-    )?;
+    if config.reject_underfunded_schedules {
+        for index in 0..config.rewards.len() {
+            assert_reward_funded(deps.as_ref(), &env, &config, &state, index)?;
+        }
+    }
+
+    store_config(deps.storage, &config)?;
+    store_state(deps.storage, &state)?;
 
     Ok(Response::default())
 }
@@ -53,14 +107,64 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> S
     match msg {
         ExecuteMsg::Receive(msg) => receive_cw20(deps, env, info, msg),
         ExecuteMsg::Unbond { amount } => unbond(deps, env, info, amount),
-        ExecuteMsg::Withdraw {} => withdraw(deps, env, info),
+        ExecuteMsg::Claim {} => claim(deps, env, info),
+        ExecuteMsg::TransferPosition { recipient, amount } => {
+            transfer_position(deps, env, info, recipient, amount)
+        }
+        ExecuteMsg::ExpireLock { staker } => expire_lock(deps, env, staker),
+        ExecuteMsg::Withdraw { staker, recipient } => withdraw(deps, env, info, staker, recipient),
+        ExecuteMsg::ApproveOperator { operator } => approve_operator(deps, info, operator),
+        ExecuteMsg::RevokeOperator { operator } => revoke_operator(deps, info, operator),
+        ExecuteMsg::AddRecipient { recipient } => add_recipient(deps, info, recipient),
+        ExecuteMsg::RemoveRecipient { recipient } => remove_recipient(deps, info, recipient),
+        ExecuteMsg::Compound {} => compound(deps, env, info),
+        ExecuteMsg::SetAutoCompound { auto_compound } => {
+            set_auto_compound(deps, env, info, auto_compound)
+        }
         ExecuteMsg::MigrateStaking {
             new_staking_contract,
         } => migrate_staking(deps, env, info, new_staking_contract),
+        ExecuteMsg::MigratePosition {} => migrate_position(deps, env, info),
+        ExecuteMsg::MigratePositions { limit } => migrate_positions(deps, env, limit),
         ExecuteMsg::UpdateConfig {
-            governance,
-            distribution_schedule,
-        } => update_config(deps, env, info, governance, distribution_schedule),
+            guardian,
+            rewards,
+            boost_curve,
+            reject_underfunded_schedules,
+            schedule_timelock,
+        } => update_config(
+            deps,
+            env,
+            info,
+            guardian,
+            rewards,
+            boost_curve,
+            reject_underfunded_schedules,
+            schedule_timelock,
+        ),
+        ExecuteMsg::ProposeGovernance { governance } => propose_governance(deps, info, governance),
+        ExecuteMsg::AcceptGovernance {} => accept_governance(deps, info),
+        ExecuteMsg::CancelGovernanceProposal {} => cancel_governance_proposal(deps, info),
+        ExecuteMsg::ExecuteScheduleChange { id } => execute_schedule_change(deps, env, id),
+        ExecuteMsg::CancelScheduleChange { id } => cancel_schedule_change(deps, info, id),
+        ExecuteMsg::AddDistributionSchedule { reward, schedule } => {
+            add_distribution_schedule(deps, env, info, reward, schedule)
+        }
+        ExecuteMsg::ExtendSchedule {
+            reward,
+            index,
+            new_end,
+            extra_amount,
+        } => extend_schedule(deps, env, info, reward, index, new_end, extra_amount),
+        ExecuteMsg::EmergencyUnbond {} => emergency_unbond(deps, env, info),
+        ExecuteMsg::SetEmergencyUnbond { enabled } => set_emergency_unbond(deps, info, enabled),
+        ExecuteMsg::Pause { operation } => pause(deps, info, operation),
+        ExecuteMsg::Unpause { operation } => unpause(deps, info, operation),
+        ExecuteMsg::Slash {
+            staker,
+            ratio,
+            recipient,
+        } => slash(deps, env, info, staker, ratio, recipient),
     }
 }
 
@@ -71,75 +175,376 @@ pub fn receive_cw20(
     cw20_msg: Cw20ReceiveMsg,
 ) -> StdResult<Response> {
     let config: Config = read_config(deps.storage)?;
+    assert_not_migrated(&config)?;
 
     match from_binary(&cw20_msg.msg) {
-        Ok(Cw20HookMsg::Bond {}) => {
+        Ok(Cw20HookMsg::Bond {
+            lock_duration,
+            beneficiary,
+        }) => {
             // only staking token contract can execute this message
             if config.staking_token != deps.api.addr_canonicalize(info.sender.as_str())? {
                 return Err(StdError::generic_err("unauthorized"));
             }
+            assert_not_paused(deps.storage, Operation::Bond)?;
 
             let cw20_sender = deps.api.addr_validate(&cw20_msg.sender)?;
-            bond(deps, env, cw20_sender, cw20_msg.amount)
+            let beneficiary = match beneficiary {
+                Some(beneficiary) => deps.api.addr_validate(&beneficiary)?,
+                None => cw20_sender.clone(),
+            };
+
+            // nobody can lock the position of someone else
+            if beneficiary != cw20_sender && lock_duration.unwrap_or_default() > 0 {
+                return Err(StdError::generic_err(
+                    "cannot lock a bond on behalf of another address",
+                ));
+            }
+
+            bond(
+                deps,
+                env,
+                cw20_sender,
+                beneficiary,
+                cw20_msg.amount,
+                lock_duration,
+            )
+        }
+        Ok(Cw20HookMsg::Fund {
+            start_time,
+            duration,
+        }) => {
+            // only reward token contracts can execute this message
+            let reward = AssetInfo::Token {
+                contract_addr: info.sender.to_string(),
+            };
+            let index = reward_asset_index(deps.as_ref(), &config, &reward)
+                .map_err(|_| StdError::generic_err("unauthorized"))?;
+
+            let funder = deps.api.addr_validate(&cw20_msg.sender)?;
+            fund(
+                deps,
+                env,
+                funder,
+                index,
+                cw20_msg.amount,
+                start_time,
+                duration,
+            )
         }
         Err(_) => Err(StdError::generic_err("data should be given")),
     }
 }
 
-pub fn bond(deps: DepsMut, env: Env, sender_addr: Addr, amount: Uint128) -> StdResult<Response> {
-    let sender_addr_raw: CanonicalAddr = deps.api.addr_canonicalize(sender_addr.as_str())?;
+pub fn bond(
+    deps: DepsMut,
+    env: Env,
+    payer: Addr,
+    beneficiary: Addr,
+    amount: Uint128,
+    lock_duration: Option<u64>,
+) -> StdResult<Response> {
+    let beneficiary_raw: CanonicalAddr = deps.api.addr_canonicalize(beneficiary.as_str())?;
 
     let config: Config = read_config(deps.storage)?;
     let mut state: State = read_state(deps.storage)?;
-    let mut staker_info: StakerInfo = read_staker_info(deps.storage, &sender_addr_raw)?;
+    let mut staker_info: StakerInfo = read_staker_info(deps.storage, &beneficiary_raw)?;
 
     // Compute global reward & staker reward
-    compute_reward(&config, &mut state, env.block.time.seconds());
+    let block_time = env.block.time.seconds();
+    compute_reward(&config, &mut state, block_time);
     compute_staker_reward(&state, &mut staker_info)?;
+    auto_compound_reward(&config, &mut state, &mut staker_info)?;
 
     // Increase bond_amount
-    increase_bond_amount(&mut state, &mut staker_info, amount);
+    let shares = bond_shares(&state, amount);
+    increase_bond_amount(&mut state, &mut staker_info, shares)?;
+
+    // A new lock can only extend the current one and
+    // the boost follows the remaining lock duration
+    match lock_duration {
+        Some(lock_duration) if lock_duration > 0 => {
+            let lock_end = std::cmp::max(staker_info.lock_end, block_time + lock_duration);
+            let boost = compute_boost(&config.boost_curve, lock_end - block_time)?;
+
+            staker_info.lock_end = lock_end;
+            update_boost(&mut state, &mut staker_info, boost)?;
+        }
+        _ => release_expired_lock(&mut state, &mut staker_info, block_time)?,
+    }
 
     // Store updated state with staker's staker_info
-    store_staker_info(deps.storage, &sender_addr_raw, &staker_info)?;
+    store_staker_info(deps.storage, &beneficiary_raw, &staker_info)?;
     store_state(deps.storage, &state)?;
 
     Ok(Response::new().add_attributes(vec![
         ("action", "bond"),
-        ("owner", sender_addr.as_str()),
+        ("payer", payer.as_str()),
+        ("beneficiary", beneficiary.as_str()),
+        ("amount", amount.to_string().as_str()),
+        ("lock_end", staker_info.lock_end.to_string().as_str()),
+    ]))
+}
+
+// append a slot paid by the received reward tokens
+pub fn fund(
+    deps: DepsMut,
+    env: Env,
+    funder: Addr,
+    index: usize,
+    amount: Uint128,
+    start_time: u64,
+    duration: u64,
+) -> StdResult<Response> {
+    let mut config: Config = read_config(deps.storage)?;
+    let mut state: State = read_state(deps.storage)?;
+    compute_reward(&config, &mut state, env.block.time.seconds());
+
+    if start_time < state.last_distributed {
+        return Err(StdError::generic_err(
+            "cannot fund a distribution in the past",
+        ));
+    }
+    let schedule = DistributionSchedule {
+        start_time,
+        end_time: start_time.checked_add(duration).ok_or_else(|| {
+            StdError::generic_err("distribution schedule must end after it starts")
+        })?,
+        amount,
+    };
+    assert_distribution_schedule(std::slice::from_ref(&schedule))?;
+
+    // finished slots no longer release anything; dropping them keeps the
+    // funded slots from growing without bound
+    let reward_info = config.rewards[index].info.to_normal(deps.api)?;
+    let funded_slots = &mut config.rewards[index].funded_slots;
+    funded_slots.retain(|funded| funded.slot.end_time > state.last_distributed);
+
+    // a full list only takes the slot by evicting a slower one,
+    // which refunds what the evicted slot has not released yet
+    let mut messages: Vec<CosmosMsg> = vec![];
+    if funded_slots.len() >= MAX_FUNDED_SLOTS {
+        let (lowest_index, lowest) = funded_slots
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, funded)| compute_slot_emission_rate(&funded.slot))
+            .unwrap();
+        if compute_slot_emission_rate(&schedule) <= compute_slot_emission_rate(&lowest.slot) {
+            return Err(StdError::generic_err(
+                "too many active distribution slots; emission rate too low to replace one",
+            ));
+        }
+
+        let evicted = funded_slots.remove(lowest_index);
+        let refund_amount = compute_distributed_amount(
+            std::slice::from_ref(&evicted.slot),
+            state.last_distributed,
+            u64::MAX,
+        );
+        if !refund_amount.is_zero() {
+            messages.push(transfer_reward_msg(
+                deps.as_ref(),
+                &reward_info,
+                deps.api.addr_humanize(&evicted.funder)?.to_string(),
+                refund_amount,
+            )?);
+        }
+    }
+    funded_slots.push(FundedSlot {
+        funder: deps.api.addr_canonicalize(funder.as_str())?,
+        slot: schedule,
+    });
+
+    store_config(deps.storage, &config)?;
+    store_state(deps.storage, &state)?;
+
+    Ok(Response::new().add_messages(messages).add_attributes(vec![
+        ("action", "fund"),
+        ("funder", funder.as_str()),
+        ("reward_token", reward_info.to_string().as_str()),
         ("amount", amount.to_string().as_str()),
+        ("start_time", start_time.to_string().as_str()),
+        ("end_time", (start_time + duration).to_string().as_str()),
     ]))
 }
 
 pub fn unbond(deps: DepsMut, env: Env, info: MessageInfo, amount: Uint128) -> StdResult<Response> {
+    assert_not_paused(deps.storage, Operation::Unbond)?;
     let config: Config = read_config(deps.storage)?;
     let sender_addr_raw: CanonicalAddr = deps.api.addr_canonicalize(info.sender.as_str())?;
 
     let mut state: State = read_state(deps.storage)?;
     let mut staker_info: StakerInfo = read_staker_info(deps.storage, &sender_addr_raw)?;
 
-    if staker_info.bond_amount < amount {
+    let shares = unbond_shares(&state, amount);
+    if staker_info.bond_amount < shares {
         return Err(StdError::generic_err("Cannot unbond more than bond amount"));
     }
 
+    let block_time = env.block.time.seconds();
+    if staker_info.lock_end > block_time {
+        return Err(StdError::generic_err("Cannot unbond before the lock ends"));
+    }
+
     // Compute global reward & staker reward
-    compute_reward(&config, &mut state, env.block.time.seconds());
+    compute_reward(&config, &mut state, block_time);
     compute_staker_reward(&state, &mut staker_info)?;
+    release_expired_lock(&mut state, &mut staker_info, block_time)?;
+    auto_compound_reward(&config, &mut state, &mut staker_info)?;
 
     // Decrease bond_amount
-    decrease_bond_amount(&mut state, &mut staker_info, amount)?;
+    decrease_bond_amount(&mut state, &mut staker_info, shares)?;
 
     // Store or remove updated rewards info
     // depends on the left pending reward and bond amount
-    if staker_info.pending_reward.is_zero() && staker_info.bond_amount.is_zero() {
+    if staker_info
+        .pending_rewards
+        .iter()
+        .all(|reward| reward.is_zero())
+        && staker_info.bond_amount.is_zero()
+    {
         remove_staker_info(deps.storage, &sender_addr_raw);
     } else {
         store_staker_info(deps.storage, &sender_addr_raw, &staker_info)?;
     }
 
+    if config.unbonding_period > 0 {
+        state.total_unbonding_amount += amount;
+    }
+
     // Store updated state
     store_state(deps.storage, &state)?;
 
+    // Without an unbonding period the tokens are returned right away,
+    // otherwise they are queued until the period has passed
+    if config.unbonding_period == 0 {
+        return Ok(Response::new()
+            .add_messages(vec![CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: deps.api.addr_humanize(&config.staking_token)?.to_string(),
+                msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: info.sender.to_string(),
+                    amount,
+                })?,
+                funds: vec![],
+            })])
+            .add_attributes(vec![
+                ("action", "unbond"),
+                ("owner", info.sender.as_str()),
+                ("amount", amount.to_string().as_str()),
+            ]));
+    }
+
+    let release_time = env.block.time.seconds() + config.unbonding_period;
+    let mut unbondings = read_unbondings(deps.storage, &sender_addr_raw)?;
+    unbondings.push(UnbondingInfo {
+        amount,
+        release_time,
+    });
+    store_unbondings(deps.storage, &sender_addr_raw, &unbondings)?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "unbond"),
+        ("owner", info.sender.as_str()),
+        ("amount", amount.to_string().as_str()),
+        ("release_time", release_time.to_string().as_str()),
+    ]))
+}
+
+// move bond between stakers; total_bond_amount stays unchanged
+pub fn transfer_position(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    recipient: String,
+    amount: Uint128,
+) -> StdResult<Response> {
+    assert_not_paused(deps.storage, Operation::Unbond)?;
+    let config: Config = read_config(deps.storage)?;
+    let sender_addr_raw: CanonicalAddr = deps.api.addr_canonicalize(info.sender.as_str())?;
+    let recipient_addr = deps.api.addr_validate(&recipient)?;
+    let recipient_raw: CanonicalAddr = deps.api.addr_canonicalize(recipient_addr.as_str())?;
+    if sender_addr_raw == recipient_raw {
+        return Err(StdError::generic_err("Cannot transfer position to self"));
+    }
+
+    let mut state: State = read_state(deps.storage)?;
+    let mut sender_info: StakerInfo = read_staker_info(deps.storage, &sender_addr_raw)?;
+    let mut recipient_info: StakerInfo = read_staker_info(deps.storage, &recipient_raw)?;
+
+    let shares = unbond_shares(&state, amount);
+    if sender_info.bond_amount < shares {
+        return Err(StdError::generic_err(
+            "Cannot transfer more than bond amount",
+        ));
+    }
+
+    let block_time = env.block.time.seconds();
+    if sender_info.lock_end > block_time {
+        return Err(StdError::generic_err(
+            "Cannot transfer before the lock ends",
+        ));
+    }
+
+    // Settle both parties against the same global reward index
+    compute_reward(&config, &mut state, block_time);
+    compute_staker_reward(&state, &mut sender_info)?;
+    release_expired_lock(&mut state, &mut sender_info, block_time)?;
+    auto_compound_reward(&config, &mut state, &mut sender_info)?;
+    compute_staker_reward(&state, &mut recipient_info)?;
+    release_expired_lock(&mut state, &mut recipient_info, block_time)?;
+    auto_compound_reward(&config, &mut state, &mut recipient_info)?;
+
+    decrease_bond_amount(&mut state, &mut sender_info, shares)?;
+    increase_bond_amount(&mut state, &mut recipient_info, shares)?;
+
+    if sender_info
+        .pending_rewards
+        .iter()
+        .all(|reward| reward.is_zero())
+        && sender_info.bond_amount.is_zero()
+    {
+        remove_staker_info(deps.storage, &sender_addr_raw);
+    } else {
+        store_staker_info(deps.storage, &sender_addr_raw, &sender_info)?;
+    }
+    store_staker_info(deps.storage, &recipient_raw, &recipient_info)?;
+    store_state(deps.storage, &state)?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "transfer_position"),
+        ("owner", info.sender.as_str()),
+        ("recipient", recipient_addr.as_str()),
+        ("amount", amount.to_string().as_str()),
+    ]))
+}
+
+// release matured unbondings to executor
+pub fn claim(deps: DepsMut, env: Env, info: MessageInfo) -> StdResult<Response> {
+    assert_not_paused(deps.storage, Operation::Unbond)?;
+    let config: Config = read_config(deps.storage)?;
+    let sender_addr_raw: CanonicalAddr = deps.api.addr_canonicalize(info.sender.as_str())?;
+
+    let block_time = env.block.time.seconds();
+    let (matured, pending): (Vec<UnbondingInfo>, Vec<UnbondingInfo>) =
+        read_unbondings(deps.storage, &sender_addr_raw)?
+            .into_iter()
+            .partition(|unbonding| unbonding.release_time <= block_time);
+
+    let amount: Uint128 = matured.iter().map(|unbonding| unbonding.amount).sum();
+    if amount.is_zero() {
+        return Err(StdError::generic_err("Nothing to claim"));
+    }
+
+    if pending.is_empty() {
+        remove_unbondings(deps.storage, &sender_addr_raw);
+    } else {
+        store_unbondings(deps.storage, &sender_addr_raw, &pending)?;
+    }
+
+    let mut state: State = read_state(deps.storage)?;
+    state.total_unbonding_amount = state.total_unbonding_amount.checked_sub(amount)?;
+    store_state(deps.storage, &state)?;
+
     Ok(Response::new()
         .add_messages(vec![CosmosMsg::Wasm(WasmMsg::Execute {
             contract_addr: deps.api.addr_humanize(&config.staking_token)?.to_string(),
@@ -150,243 +555,1483 @@ pub fn unbond(deps: DepsMut, env: Env, info: MessageInfo, amount: Uint128) -> St
             funds: vec![],
         })])
         .add_attributes(vec![
-            ("action", "unbond"),
+            ("action", "claim"),
             ("owner", info.sender.as_str()),
             ("amount", amount.to_string().as_str()),
         ]))
 }
 
-// withdraw rewards to executor
-pub fn withdraw(deps: DepsMut, env: Env, info: MessageInfo) -> StdResult<Response> {
-    let sender_addr_raw = deps.api.addr_canonicalize(info.sender.as_str())?;
+// withdraw rewards of the staker to the staker or a recipient
+pub fn withdraw(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    staker: Option<String>,
+    recipient: Option<String>,
+) -> StdResult<Response> {
+    assert_not_paused(deps.storage, Operation::Withdraw)?;
+    let staker = match staker {
+        Some(staker) => deps.api.addr_validate(&staker)?,
+        None => info.sender.clone(),
+    };
+    let recipient = match recipient {
+        Some(recipient) => deps.api.addr_validate(&recipient)?,
+        None => staker.clone(),
+    };
+    let staker_raw = deps.api.addr_canonicalize(staker.as_str())?;
+
+    // operators can only pay the staker or a whitelisted recipient
+    if info.sender != staker {
+        let sender_raw = deps.api.addr_canonicalize(info.sender.as_str())?;
+        let recipient_raw = deps.api.addr_canonicalize(recipient.as_str())?;
+        let permissions = read_withdraw_permissions(deps.storage, &staker_raw)?;
+        if !permissions.operators.contains(&sender_raw) {
+            return Err(StdError::generic_err("unauthorized"));
+        }
+        if recipient != staker && !permissions.recipients.contains(&recipient_raw) {
+            return Err(StdError::generic_err(
+                "recipient is not whitelisted by the staker",
+            ));
+        }
+    }
 
     let config: Config = read_config(deps.storage)?;
     let mut state: State = read_state(deps.storage)?;
-    let mut staker_info = read_staker_info(deps.storage, &sender_addr_raw)?;
+    let mut staker_info = read_staker_info(deps.storage, &staker_raw)?;
 
     // Compute global reward & staker reward
     compute_reward(&config, &mut state, env.block.time.seconds());
     compute_staker_reward(&state, &mut staker_info)?;
+    release_expired_lock(&mut state, &mut staker_info, env.block.time.seconds())?;
+    auto_compound_reward(&config, &mut state, &mut staker_info)?;
+
+    let mut messages: Vec<CosmosMsg> = vec![];
+    let mut attributes = vec![
+        ("action", "withdraw".to_string()),
+        ("owner", staker.to_string()),
+        ("recipient", recipient.to_string()),
+    ];
+    for ((reward, pending_reward), unclaimed_reward) in config
+        .rewards
+        .iter()
+        .zip(staker_info.pending_rewards.iter_mut())
+        .zip(state.unclaimed_rewards.iter_mut())
+    {
+        if pending_reward.is_zero() {
+            continue;
+        }
 
-    let amount = staker_info.pending_reward;
-    staker_info.pending_reward = Uint128::zero();
+        let reward_info = reward.info.to_normal(deps.api)?;
+        messages.push(transfer_reward_msg(
+            deps.as_ref(),
+            &reward_info,
+            recipient.to_string(),
+            *pending_reward,
+        )?);
+        *unclaimed_reward = unclaimed_reward.saturating_sub(*pending_reward);
+        attributes.push(("reward_token", reward_info.to_string()));
+        attributes.push(("amount", pending_reward.to_string()));
+
+        *pending_reward = Uint128::zero();
+    }
 
     // Store or remove updated rewards info
     // depends on the left pending reward and bond amount
     if staker_info.bond_amount.is_zero() {
-        remove_staker_info(deps.storage, &sender_addr_raw);
+        remove_staker_info(deps.storage, &staker_raw);
     } else {
-        store_staker_info(deps.storage, &sender_addr_raw, &staker_info)?;
+        store_staker_info(deps.storage, &staker_raw, &staker_info)?;
     }
 
     // Store updated state
     store_state(deps.storage, &state)?;
 
     Ok(Response::new()
-        .add_messages(vec![CosmosMsg::Wasm(WasmMsg::Execute {
-            contract_addr: deps.api.addr_humanize(&config.reward_token)?.to_string(),
-            msg: to_binary(&Cw20ExecuteMsg::Transfer {
-                recipient: info.sender.to_string(),
-                amount,
-            })?,
-            funds: vec![],
-        })])
-        .add_attributes(vec![
-            ("action", "withdraw"),
-            ("owner", info.sender.as_str()),
-            ("amount", amount.to_string().as_str()),
-        ]))
+        .add_messages(messages)
+        .add_attributes(attributes))
 }
 
-pub fn update_config(
-    deps: DepsMut,
-    _env: Env,
-    info: MessageInfo,
-    governance: Option<String>,
-    distribution_schedule: Option<Vec<(u64, u64, Uint128)>>,
-) -> StdResult<Response> {
-    let mut config: Config = read_config(deps.storage)?;
+pub fn approve_operator(deps: DepsMut, info: MessageInfo, operator: String) -> StdResult<Response> {
+    let sender_addr_raw = deps.api.addr_canonicalize(info.sender.as_str())?;
+    let operator_raw = deps.api.addr_canonicalize(&operator)?;
 
-    let sender_addr_raw: CanonicalAddr = deps.api.addr_canonicalize(info.sender.as_str())?;
-    if config.governance != sender_addr_raw {
-        return Err(StdError::generic_err("unauthorized"));
+    let mut permissions = read_withdraw_permissions(deps.storage, &sender_addr_raw)?;
+    if !permissions.operators.contains(&operator_raw) {
+        permissions.operators.push(operator_raw);
     }
+    store_withdraw_permissions(deps.storage, &sender_addr_raw, &permissions)?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "approve_operator"),
+        ("owner", info.sender.as_str()),
+        ("operator", operator.as_str()),
+    ]))
+}
 
-    if let Some(distribution_schedule) = distribution_schedule {
-        let state: State = read_state(deps.storage)?;
+pub fn revoke_operator(deps: DepsMut, info: MessageInfo, operator: String) -> StdResult<Response> {
+    let sender_addr_raw = deps.api.addr_canonicalize(info.sender.as_str())?;
+    let operator_raw = deps.api.addr_canonicalize(&operator)?;
 
-        assert_new_schedules(&config, &state, distribution_schedule.clone())?;
+    let mut permissions = read_withdraw_permissions(deps.storage, &sender_addr_raw)?;
+    permissions.operators.retain(|addr| *addr != operator_raw);
+    store_withdraw_permissions(deps.storage, &sender_addr_raw, &permissions)?;
 
-        config.distribution_schedule = distribution_schedule;
-    }
-    if let Some(governance) = governance {
-        config.governance = deps.api.addr_canonicalize(&governance)?;
-    }
+    Ok(Response::new().add_attributes(vec![
+        ("action", "revoke_operator"),
+        ("owner", info.sender.as_str()),
+        ("operator", operator.as_str()),
+    ]))
+}
 
-    store_config(deps.storage, &config)?;
+pub fn add_recipient(deps: DepsMut, info: MessageInfo, recipient: String) -> StdResult<Response> {
+    let sender_addr_raw = deps.api.addr_canonicalize(info.sender.as_str())?;
+    let recipient_raw = deps.api.addr_canonicalize(&recipient)?;
 
-    Ok(Response::new().add_attributes(vec![("action", "update_config")]))
+    let mut permissions = read_withdraw_permissions(deps.storage, &sender_addr_raw)?;
+    if !permissions.recipients.contains(&recipient_raw) {
+        permissions.recipients.push(recipient_raw);
+    }
+    store_withdraw_permissions(deps.storage, &sender_addr_raw, &permissions)?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "add_recipient"),
+        ("owner", info.sender.as_str()),
+        ("recipient", recipient.as_str()),
+    ]))
 }
 
-pub fn migrate_staking(
+pub fn remove_recipient(
     deps: DepsMut,
-    env: Env,
     info: MessageInfo,
-    new_staking_contract: String,
+    recipient: String,
 ) -> StdResult<Response> {
-    let sender_addr_raw: CanonicalAddr = deps.api.addr_canonicalize(info.sender.as_str())?;
-    let mut config: Config = read_config(deps.storage)?;
+    let sender_addr_raw = deps.api.addr_canonicalize(info.sender.as_str())?;
+    let recipient_raw = deps.api.addr_canonicalize(&recipient)?;
 
-    if sender_addr_raw != config.governance {
-        return Err(StdError::generic_err("unauthorized"));
-    }
+    let mut permissions = read_withdraw_permissions(deps.storage, &sender_addr_raw)?;
+    permissions.recipients.retain(|addr| *addr != recipient_raw);
+    store_withdraw_permissions(deps.storage, &sender_addr_raw, &permissions)?;
 
-    let mut state: State = read_state(deps.storage)?;
+    Ok(Response::new().add_attributes(vec![
+        ("action", "remove_recipient"),
+        ("owner", info.sender.as_str()),
+        ("recipient", recipient.as_str()),
+    ]))
+}
 
-    // compute global reward, sets last_distributed_seconds to env.block.time.seconds
-    compute_reward(&config, &mut state, env.block.time.seconds());
+// bond the staking token reward of executor
+pub fn compound(deps: DepsMut, env: Env, info: MessageInfo) -> StdResult<Response> {
+    assert_not_paused(deps.storage, Operation::Bond)?;
+    let sender_addr_raw = deps.api.addr_canonicalize(info.sender.as_str())?;
 
-    let total_distribution_amount: Uint128 =
-        config.distribution_schedule.iter().map(|item| item.2).sum();
+    let config: Config = read_config(deps.storage)?;
+    assert_not_migrated(&config)?;
+    let mut state: State = read_state(deps.storage)?;
+    let mut staker_info = read_staker_info(deps.storage, &sender_addr_raw)?;
 
     let block_time = env.block.time.seconds();
-    // eliminate distribution slots that have not started
-    config
-        .distribution_schedule
-        .retain(|slot| slot.0 < block_time);
-
-    let mut distributed_amount = Uint128::zero();
-    for s in config.distribution_schedule.iter_mut() {
-        if s.1 < block_time {
-            // all distributed
-            distributed_amount += s.2;
-        } else {
-            // partially distributed slot
-            let whole_time = s.1 - s.0;
-            let distribution_amount_per_second: Decimal = Decimal::from_ratio(s.2, whole_time);
-
-            let passed_time = block_time - s.0;
-            let distributed_amount_on_slot =
-                distribution_amount_per_second * Uint128::from(passed_time as u128);
-            distributed_amount += distributed_amount_on_slot;
+    compute_reward(&config, &mut state, block_time);
+    compute_staker_reward(&state, &mut staker_info)?;
+    release_expired_lock(&mut state, &mut staker_info, block_time)?;
 
-            // modify distribution slot
-            s.1 = block_time;
-            s.2 = distributed_amount_on_slot;
-        }
+    let amount = compound_reward(&config, &mut state, &mut staker_info)?;
+    if amount.is_zero() {
+        return Err(StdError::generic_err("Nothing to compound"));
     }
 
-    // update config
-    store_config(deps.storage, &config)?;
-    // update state
+    store_staker_info(deps.storage, &sender_addr_raw, &staker_info)?;
     store_state(deps.storage, &state)?;
 
-    let remaining_anc = total_distribution_amount.checked_sub(distributed_amount)?;
-
-    let reward_token: Addr = deps.api.addr_humanize(&config.reward_token)?;
-
-    Ok(Response::new()
-        .add_messages(vec![CosmosMsg::Wasm(WasmMsg::Execute {
-            contract_addr: reward_token.to_string(),
-            msg: to_binary(&Cw20ExecuteMsg::Transfer {
-                recipient: new_staking_contract,
-                amount: remaining_anc,
-            })?,
-            funds: vec![],
-        })])
-        .add_attributes(vec![
-            ("action", "migrate_staking"),
-            ("distributed_amount", &distributed_amount.to_string()),
-            ("remaining_amount", &remaining_anc.to_string()),
-        ]))
-}
-
-fn increase_bond_amount(state: &mut State, staker_info: &mut StakerInfo, amount: Uint128) {
-    state.total_bond_amount += amount;
-    staker_info.bond_amount += amount;
+    Ok(Response::new().add_attributes(vec![
+        ("action", "compound"),
+        ("owner", info.sender.as_str()),
+        ("amount", amount.to_string().as_str()),
+    ]))
 }
 
-fn decrease_bond_amount(
-    state: &mut State,
-    staker_info: &mut StakerInfo,
-    amount: Uint128,
-) -> StdResult<()> {
-    state.total_bond_amount = state.total_bond_amount.checked_sub(amount)?;
-    staker_info.bond_amount = staker_info.bond_amount.checked_sub(amount)?;
-    Ok(())
-}
+pub fn set_auto_compound(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    auto_compound: bool,
+) -> StdResult<Response> {
+    let sender_addr_raw = deps.api.addr_canonicalize(info.sender.as_str())?;
 
-// compute distributed rewards and update global reward index
-fn compute_reward(config: &Config, state: &mut State, block_time: u64) {
-    if state.total_bond_amount.is_zero() {
-        state.last_distributed = block_time;
-        return;
+    let config: Config = read_config(deps.storage)?;
+    if auto_compound && compounding_reward_index(&config).is_none() {
+        return Err(StdError::generic_err(
+            "cannot compound; no reward asset matches the staking token",
+        ));
     }
 
-    let mut distributed_amount: Uint128 = Uint128::zero();
-    for s in config.distribution_schedule.iter() {
-        if s.0 > block_time || s.1 < state.last_distributed {
-            continue;
-        }
-
-        // min(s.1, block_time) - max(s.0, last_distributed)
-        let passed_time =
-            std::cmp::min(s.1, block_time) - std::cmp::max(s.0, state.last_distributed);
-
-        let time = s.1 - s.0;
-        let distribution_amount_per_second: Decimal = Decimal::from_ratio(s.2, time);
-        distributed_amount += distribution_amount_per_second * Uint128::from(passed_time as u128);
+    let mut staker_info = read_staker_info(deps.storage, &sender_addr_raw)?;
+    if staker_info.bond_amount.is_zero() {
+        return Err(StdError::generic_err("Nothing bonded"));
     }
 
-    state.last_distributed = block_time;
-    state.global_reward_index = state.global_reward_index
-        + Decimal::from_ratio(distributed_amount, state.total_bond_amount);
+    staker_info.auto_compound = auto_compound;
+    store_staker_info(deps.storage, &sender_addr_raw, &staker_info)?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "set_auto_compound"),
+        ("owner", info.sender.as_str()),
+        ("auto_compound", auto_compound.to_string().as_str()),
+    ]))
 }
 
-// withdraw reward to pending reward
-fn compute_staker_reward(state: &State, staker_info: &mut StakerInfo) -> StdResult<()> {
-    let pending_reward = (staker_info.bond_amount * state.global_reward_index)
-        .checked_sub(staker_info.bond_amount * staker_info.reward_index)?;
+// drop the boost of an ended lock so it stops earning boosted rewards
+pub fn expire_lock(deps: DepsMut, env: Env, staker: String) -> StdResult<Response> {
+    let staker_raw: CanonicalAddr = deps.api.addr_canonicalize(&staker)?;
 
-    staker_info.reward_index = state.global_reward_index;
-    staker_info.pending_reward += pending_reward;
-    Ok(())
-}
+    let config: Config = read_config(deps.storage)?;
+    let mut state: State = read_state(deps.storage)?;
+    let mut staker_info: StakerInfo = read_staker_info(deps.storage, &staker_raw)?;
+
+    let block_time = env.block.time.seconds();
+    if staker_info.lock_end == 0 || staker_info.lock_end > block_time {
+        return Err(StdError::generic_err("No ended lock to expire"));
+    }
+
+    // Settle rewards at the boosted weight before dropping the boost
+    compute_reward(&config, &mut state, block_time);
+    compute_staker_reward(&state, &mut staker_info)?;
+    release_expired_lock(&mut state, &mut staker_info, block_time)?;
+    auto_compound_reward(&config, &mut state, &mut staker_info)?;
+
+    store_staker_info(deps.storage, &staker_raw, &staker_info)?;
+    store_state(deps.storage, &state)?;
+
+    Ok(
+        Response::new()
+            .add_attributes(vec![("action", "expire_lock"), ("staker", staker.as_str())]),
+    )
+}
+
+// return principal while unbonding is paused; pending rewards are
+// left untouched and the staker forfeits what was not settled yet
+pub fn emergency_unbond(deps: DepsMut, env: Env, info: MessageInfo) -> StdResult<Response> {
+    let pause_info = read_pause_info(deps.storage)?;
+    if !pause_info.unbond {
+        return Err(StdError::generic_err(
+            "emergency unbond is only available while unbond is paused",
+        ));
+    }
+    if !pause_info.emergency_unbond {
+        return Err(StdError::generic_err(
+            "emergency unbond is not enabled by governance",
+        ));
+    }
+
+    let config: Config = read_config(deps.storage)?;
+    let sender_addr_raw: CanonicalAddr = deps.api.addr_canonicalize(info.sender.as_str())?;
+
+    let mut state: State = read_state(deps.storage)?;
+    let mut staker_info: StakerInfo = read_staker_info(deps.storage, &sender_addr_raw)?;
+
+    // settle rewards up to now so the bond does not leave them to others
+    compute_reward(&config, &mut state, env.block.time.seconds());
+    compute_staker_reward(&state, &mut staker_info)?;
+
+    let shares = staker_info.bond_amount;
+    let bond_amount = bond_tokens(&state, shares);
+    decrease_bond_amount(&mut state, &mut staker_info, shares)?;
+    staker_info.boost = Decimal::one();
+    staker_info.lock_end = 0;
+
+    let unbonding_amount: Uint128 = read_unbondings(deps.storage, &sender_addr_raw)?
+        .iter()
+        .map(|unbonding| unbonding.amount)
+        .sum();
+    remove_unbondings(deps.storage, &sender_addr_raw);
+    state.total_unbonding_amount = state.total_unbonding_amount.checked_sub(unbonding_amount)?;
+
+    let amount = bond_amount + unbonding_amount;
+    if amount.is_zero() {
+        return Err(StdError::generic_err("Nothing to unbond"));
+    }
+
+    if staker_info
+        .pending_rewards
+        .iter()
+        .all(|reward| reward.is_zero())
+    {
+        remove_staker_info(deps.storage, &sender_addr_raw);
+    } else {
+        store_staker_info(deps.storage, &sender_addr_raw, &staker_info)?;
+    }
+    store_state(deps.storage, &state)?;
+
+    Ok(Response::new()
+        .add_messages(vec![CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: deps.api.addr_humanize(&config.staking_token)?.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: info.sender.to_string(),
+                amount,
+            })?,
+            funds: vec![],
+        })])
+        .add_attributes(vec![
+            ("action", "emergency_unbond"),
+            ("owner", info.sender.as_str()),
+            ("amount", amount.to_string().as_str()),
+        ]))
+}
+
+// a pool-wide slash only lowers the share price, so its cost
+// does not depend on the number of stakers
+pub fn slash(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    staker: Option<String>,
+    ratio: Decimal,
+    recipient: Option<String>,
+) -> StdResult<Response> {
+    let config: Config = read_config(deps.storage)?;
+    if deps.api.addr_canonicalize(info.sender.as_str())? != config.governance {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+    if ratio > Decimal::one() {
+        return Err(StdError::generic_err("slash ratio cannot exceed one"));
+    }
+
+    let mut state: State = read_state(deps.storage)?;
+
+    // compute global reward, sets last_distributed_seconds to env.block.time.seconds
+    compute_reward(&config, &mut state, env.block.time.seconds());
+
+    let amount = match &staker {
+        Some(staker) => {
+            let staker_raw = deps.api.addr_canonicalize(staker)?;
+            let mut staker_info: StakerInfo = read_staker_info(deps.storage, &staker_raw)?;
+
+            // Compute staker reward before the bond changes
+            compute_staker_reward(&state, &mut staker_info)?;
+
+            let tokens_before = bond_tokens(&state, staker_info.bond_amount);
+            let shares = staker_info.bond_amount * ratio;
+            decrease_bond_amount(&mut state, &mut staker_info, shares)?;
+            store_staker_info(deps.storage, &staker_raw, &staker_info)?;
+            tokens_before.checked_sub(bond_tokens(&state, staker_info.bond_amount))?
+        }
+        None => {
+            if ratio == Decimal::one() {
+                return Err(StdError::generic_err(
+                    "pool-wide slash ratio must be less than one",
+                ));
+            }
+
+            let tokens_before = bond_tokens(&state, state.total_bond_amount);
+            state.share_price = state.share_price * Decimal256::from(Decimal::one() - ratio);
+            tokens_before.checked_sub(bond_tokens(&state, state.total_bond_amount))?
+        }
+    };
+
+    if amount.is_zero() {
+        return Err(StdError::generic_err("Nothing to slash"));
+    }
+
+    store_state(deps.storage, &state)?;
+
+    let staking_token = deps.api.addr_humanize(&config.staking_token)?.to_string();
+    let msg = match &recipient {
+        Some(recipient) => Cw20ExecuteMsg::Transfer {
+            recipient: deps.api.addr_validate(recipient)?.to_string(),
+            amount,
+        },
+        None => Cw20ExecuteMsg::Burn { amount },
+    };
+
+    Ok(Response::new()
+        .add_message(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: staking_token,
+            msg: to_binary(&msg)?,
+            funds: vec![],
+        }))
+        .add_attributes(vec![
+            ("action", "slash"),
+            ("staker", staker.as_deref().unwrap_or("pool")),
+            ("amount", amount.to_string().as_str()),
+        ]))
+}
+
+pub fn pause(deps: DepsMut, info: MessageInfo, operation: Operation) -> StdResult<Response> {
+    let config: Config = read_config(deps.storage)?;
+    let sender_addr_raw: CanonicalAddr = deps.api.addr_canonicalize(info.sender.as_str())?;
+    if sender_addr_raw != config.governance && Some(sender_addr_raw) != config.guardian {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    let mut pause_info = read_pause_info(deps.storage)?;
+    pause_info.set_paused(&operation, true);
+    store_pause_info(deps.storage, &pause_info)?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "pause"),
+        ("operation", operation.to_string().as_str()),
+    ]))
+}
+
+pub fn set_emergency_unbond(
+    deps: DepsMut,
+    info: MessageInfo,
+    enabled: bool,
+) -> StdResult<Response> {
+    let config: Config = read_config(deps.storage)?;
+    if deps.api.addr_canonicalize(info.sender.as_str())? != config.governance {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    let mut pause_info = read_pause_info(deps.storage)?;
+    pause_info.emergency_unbond = enabled;
+    store_pause_info(deps.storage, &pause_info)?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "set_emergency_unbond"),
+        ("enabled", enabled.to_string().as_str()),
+    ]))
+}
+
+pub fn unpause(deps: DepsMut, info: MessageInfo, operation: Operation) -> StdResult<Response> {
+    let config: Config = read_config(deps.storage)?;
+    let sender_addr_raw: CanonicalAddr = deps.api.addr_canonicalize(info.sender.as_str())?;
+    if sender_addr_raw != config.governance {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    let mut pause_info = read_pause_info(deps.storage)?;
+    pause_info.set_paused(&operation, false);
+    store_pause_info(deps.storage, &pause_info)?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "unpause"),
+        ("operation", operation.to_string().as_str()),
+    ]))
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn update_config(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    guardian: Option<String>,
+    rewards: Option<Vec<RewardAsset>>,
+    boost_curve: Option<Vec<(u64, Decimal)>>,
+    reject_underfunded_schedules: Option<bool>,
+    schedule_timelock: Option<u64>,
+) -> StdResult<Response> {
+    let mut config: Config = read_config(deps.storage)?;
+
+    let sender_addr_raw: CanonicalAddr = deps.api.addr_canonicalize(info.sender.as_str())?;
+    if config.governance != sender_addr_raw {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    let mut attributes = vec![("action", "update_config".to_string())];
+    if let Some(rewards) = rewards {
+        if config.schedule_timelock > 0 {
+            // fail now rather than once the timelock has passed
+            update_rewards(deps.as_ref(), &mut config.clone(), rewards.clone())?;
+            let id = queue_schedule_change(
+                deps.storage,
+                &env,
+                &config,
+                ScheduleChange::UpdateRewards { rewards },
+            )?;
+            attributes.push(("schedule_change_id", id.to_string()));
+        } else {
+            update_rewards(deps.as_ref(), &mut config, rewards)?;
+        }
+    }
+    if let Some(schedule_timelock) = schedule_timelock {
+        if config.schedule_timelock > 0 {
+            let id = queue_schedule_change(
+                deps.storage,
+                &env,
+                &config,
+                ScheduleChange::UpdateTimelock { schedule_timelock },
+            )?;
+            attributes.push(("schedule_change_id", id.to_string()));
+        } else {
+            config.schedule_timelock = schedule_timelock;
+        }
+    }
+    if let Some(boost_curve) = boost_curve {
+        assert_boost_curve(&boost_curve)?;
+        config.boost_curve = boost_curve;
+    }
+    if let Some(guardian) = guardian {
+        config.guardian = Some(deps.api.addr_canonicalize(&guardian)?);
+    }
+    if let Some(reject_underfunded_schedules) = reject_underfunded_schedules {
+        config.reject_underfunded_schedules = reject_underfunded_schedules;
+    }
+
+    if config.reject_underfunded_schedules {
+        assert_rewards_funded(deps.as_ref(), &env, &config)?;
+    }
+
+    store_config(deps.storage, &config)?;
+
+    Ok(Response::new().add_attributes(attributes))
+}
+
+// replace the reward assets after checking no started distribution changes
+fn update_rewards(deps: Deps, config: &mut Config, rewards: Vec<RewardAsset>) -> StdResult<()> {
+    assert_not_migrated(config)?;
+    let state: State = read_state(deps.storage)?;
+
+    if rewards.len() < config.rewards.len() {
+        return Err(StdError::generic_err(
+            "cannot update; existing reward assets cannot be removed",
+        ));
+    }
+
+    let mut rewards = rewards
+        .iter()
+        .map(|reward| RewardAssetRaw::from_normal(deps.api, reward))
+        .collect::<StdResult<Vec<RewardAssetRaw>>>()?;
+    assert_unique_reward_tokens(&rewards)?;
+
+    for (i, reward) in rewards.iter_mut().enumerate() {
+        assert_distribution_schedule(&reward.distribution_schedule)?;
+
+        // newly appended reward assets have no schedule yet
+        let current_schedule = match config.rewards.get(i) {
+            Some(current) => {
+                if !current.info.equal(&reward.info) {
+                    return Err(StdError::generic_err(
+                        "cannot update; existing reward assets cannot be replaced",
+                    ));
+                }
+                // funded slots are not part of the governance schedule
+                reward.funded_slots = current.funded_slots.clone();
+                current.distribution_schedule.clone()
+            }
+            None => vec![],
+        };
+
+        assert_new_schedules(
+            &current_schedule,
+            &state,
+            reward.distribution_schedule.clone(),
+        )?;
+    }
+
+    config.rewards = rewards;
+    Ok(())
+}
+
+pub fn add_distribution_schedule(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    reward: AssetInfo,
+    schedule: DistributionSchedule,
+) -> StdResult<Response> {
+    let config: Config = read_config(deps.storage)?;
+    if config.governance != deps.api.addr_canonicalize(info.sender.as_str())? {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    if config.schedule_timelock > 0 {
+        reward_asset_index(deps.as_ref(), &config, &reward)?;
+        assert_distribution_schedule(std::slice::from_ref(&schedule))?;
+        // a slot started by its eta could never be executed
+        if schedule.start_time <= env.block.time.seconds() + config.schedule_timelock {
+            return Err(StdError::generic_err(
+                "distribution schedule must start after the timelock",
+            ));
+        }
+
+        let id = queue_schedule_change(
+            deps.storage,
+            &env,
+            &config,
+            ScheduleChange::AddDistributionSchedule { reward, schedule },
+        )?;
+        return Ok(Response::new().add_attributes(vec![
+            ("action", "add_distribution_schedule".to_string()),
+            ("schedule_change_id", id.to_string()),
+        ]));
+    }
+
+    apply_add_distribution_schedule(deps, env, reward, schedule)
+}
+
+fn apply_add_distribution_schedule(
+    deps: DepsMut,
+    env: Env,
+    reward: AssetInfo,
+    schedule: DistributionSchedule,
+) -> StdResult<Response> {
+    let mut config: Config = read_config(deps.storage)?;
+    assert_not_migrated(&config)?;
+
+    // settle the past before the schedule changes
+    let mut state: State = read_state(deps.storage)?;
+    compute_reward(&config, &mut state, env.block.time.seconds());
+
+    let index = reward_asset_index(deps.as_ref(), &config, &reward)?;
+    assert_distribution_schedule(std::slice::from_ref(&schedule))?;
+    if schedule.start_time <= state.last_distributed {
+        return Err(StdError::generic_err(
+            "cannot add a distribution that has already started",
+        ));
+    }
+
+    let amount = schedule.amount;
+    config.rewards[index].distribution_schedule.push(schedule);
+    assert_reward_funded(deps.as_ref(), &env, &config, &state, index)?;
+
+    store_config(deps.storage, &config)?;
+    store_state(deps.storage, &state)?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "add_distribution_schedule"),
+        ("reward_token", reward.to_string().as_str()),
+        ("amount", amount.to_string().as_str()),
+    ]))
+}
+
+pub fn extend_schedule(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    reward: AssetInfo,
+    index: u32,
+    new_end: u64,
+    extra_amount: Uint128,
+) -> StdResult<Response> {
+    let config: Config = read_config(deps.storage)?;
+    if config.governance != deps.api.addr_canonicalize(info.sender.as_str())? {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    if config.schedule_timelock > 0 {
+        // the slot is queued by value as indexes move when slots are pruned
+        let reward_index = reward_asset_index(deps.as_ref(), &config, &reward)?;
+        let slot = config.rewards[reward_index]
+            .distribution_schedule
+            .get(index as usize)
+            .cloned()
+            .ok_or_else(|| StdError::generic_err("distribution schedule not found"))?;
+        if new_end <= slot.end_time {
+            return Err(StdError::generic_err(
+                "new end must be after the current end",
+            ));
+        }
+
+        let id = queue_schedule_change(
+            deps.storage,
+            &env,
+            &config,
+            ScheduleChange::ExtendSchedule {
+                reward,
+                slot,
+                new_end,
+                extra_amount,
+            },
+        )?;
+        return Ok(Response::new().add_attributes(vec![
+            ("action", "extend_schedule".to_string()),
+            ("schedule_change_id", id.to_string()),
+        ]));
+    }
+
+    apply_extend_schedule(deps, env, reward, index as usize, new_end, extra_amount)
+}
+
+fn apply_extend_schedule(
+    deps: DepsMut,
+    env: Env,
+    reward: AssetInfo,
+    index: usize,
+    new_end: u64,
+    extra_amount: Uint128,
+) -> StdResult<Response> {
+    let mut config: Config = read_config(deps.storage)?;
+    assert_not_migrated(&config)?;
+
+    // settle the past before the schedule changes
+    let mut state: State = read_state(deps.storage)?;
+    let block_time = env.block.time.seconds();
+    compute_reward(&config, &mut state, block_time);
+
+    let reward_index = reward_asset_index(deps.as_ref(), &config, &reward)?;
+    let distribution_schedule = &mut config.rewards[reward_index].distribution_schedule;
+    let slot = distribution_schedule
+        .get_mut(index)
+        .ok_or_else(|| StdError::generic_err("distribution schedule not found"))?;
+    if slot.end_time <= block_time {
+        return Err(StdError::generic_err(
+            "cannot extend a finished distribution",
+        ));
+    }
+    if new_end <= slot.end_time {
+        return Err(StdError::generic_err(
+            "new end must be after the current end",
+        ));
+    }
+
+    if slot.start_time < block_time {
+        // keep what was released so far and move the rest to a new slot
+        let distributed_amount = compute_slot_distributed_amount(slot, block_time);
+        let remaining_amount = slot.amount.checked_sub(distributed_amount)?;
+        slot.end_time = block_time;
+        slot.amount = distributed_amount;
+
+        distribution_schedule.push(DistributionSchedule {
+            start_time: block_time,
+            end_time: new_end,
+            amount: remaining_amount + extra_amount,
+        });
+    } else {
+        slot.end_time = new_end;
+        slot.amount += extra_amount;
+    }
+    assert_reward_funded(deps.as_ref(), &env, &config, &state, reward_index)?;
+
+    store_config(deps.storage, &config)?;
+    store_state(deps.storage, &state)?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "extend_schedule"),
+        ("reward_token", reward.to_string().as_str()),
+        ("new_end", new_end.to_string().as_str()),
+        ("extra_amount", extra_amount.to_string().as_str()),
+    ]))
+}
+
+pub fn propose_governance(
+    deps: DepsMut,
+    info: MessageInfo,
+    governance: String,
+) -> StdResult<Response> {
+    let mut config: Config = read_config(deps.storage)?;
+    if config.governance != deps.api.addr_canonicalize(info.sender.as_str())? {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    let governance = deps.api.addr_validate(&governance)?;
+    config.pending_governance = Some(deps.api.addr_canonicalize(governance.as_str())?);
+    store_config(deps.storage, &config)?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "propose_governance"),
+        ("pending_governance", governance.as_str()),
+    ]))
+}
+
+pub fn accept_governance(deps: DepsMut, info: MessageInfo) -> StdResult<Response> {
+    let mut config: Config = read_config(deps.storage)?;
+    let sender_addr_raw = deps.api.addr_canonicalize(info.sender.as_str())?;
+    if config.pending_governance != Some(sender_addr_raw.clone()) {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    config.governance = sender_addr_raw;
+    config.pending_governance = None;
+    store_config(deps.storage, &config)?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "accept_governance"),
+        ("governance", info.sender.as_str()),
+    ]))
+}
+
+pub fn cancel_governance_proposal(deps: DepsMut, info: MessageInfo) -> StdResult<Response> {
+    let mut config: Config = read_config(deps.storage)?;
+    if config.governance != deps.api.addr_canonicalize(info.sender.as_str())? {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+    if config.pending_governance.is_none() {
+        return Err(StdError::generic_err("no pending governance"));
+    }
+
+    config.pending_governance = None;
+    store_config(deps.storage, &config)?;
+
+    Ok(Response::new().add_attributes(vec![("action", "cancel_governance_proposal")]))
+}
+
+// queue a schedule change until the schedule timelock has passed
+fn queue_schedule_change(
+    storage: &mut dyn Storage,
+    env: &Env,
+    config: &Config,
+    change: ScheduleChange,
+) -> StdResult<u64> {
+    store_schedule_change(
+        storage,
+        &QueuedScheduleChange {
+            change,
+            eta: env.block.time.seconds() + config.schedule_timelock,
+        },
+    )
+}
+
+pub fn execute_schedule_change(deps: DepsMut, env: Env, id: u64) -> StdResult<Response> {
+    let queued_change = read_schedule_change(deps.storage, id)?;
+    if env.block.time.seconds() < queued_change.eta {
+        return Err(StdError::generic_err("schedule change is still timelocked"));
+    }
+    if env.block.time.seconds() > queued_change.eta + SCHEDULE_CHANGE_GRACE_PERIOD {
+        return Err(StdError::generic_err("schedule change has expired"));
+    }
+    remove_schedule_change(deps.storage, id);
+
+    let res = match queued_change.change {
+        ScheduleChange::AddDistributionSchedule { reward, schedule } => {
+            apply_add_distribution_schedule(deps, env, reward, schedule)?
+        }
+        ScheduleChange::ExtendSchedule {
+            reward,
+            slot,
+            new_end,
+            extra_amount,
+        } => {
+            let config: Config = read_config(deps.storage)?;
+            let reward_index = reward_asset_index(deps.as_ref(), &config, &reward)?;
+            let index = config.rewards[reward_index]
+                .distribution_schedule
+                .iter()
+                .position(|item| *item == slot)
+                .ok_or_else(|| StdError::generic_err("distribution schedule not found"))?;
+            apply_extend_schedule(deps, env, reward, index, new_end, extra_amount)?
+        }
+        ScheduleChange::UpdateRewards { rewards } => {
+            let mut config: Config = read_config(deps.storage)?;
+            update_rewards(deps.as_ref(), &mut config, rewards)?;
+            if config.reject_underfunded_schedules {
+                assert_rewards_funded(deps.as_ref(), &env, &config)?;
+            }
+            store_config(deps.storage, &config)?;
+            Response::new().add_attribute("action", "update_rewards")
+        }
+        ScheduleChange::UpdateTimelock { schedule_timelock } => {
+            let mut config: Config = read_config(deps.storage)?;
+            config.schedule_timelock = schedule_timelock;
+            store_config(deps.storage, &config)?;
+            Response::new().add_attributes(vec![
+                ("action", "update_schedule_timelock"),
+                ("schedule_timelock", schedule_timelock.to_string().as_str()),
+            ])
+        }
+    };
+
+    Ok(res.add_attribute("schedule_change_id", id.to_string()))
+}
+
+pub fn cancel_schedule_change(deps: DepsMut, info: MessageInfo, id: u64) -> StdResult<Response> {
+    let config: Config = read_config(deps.storage)?;
+    if config.governance != deps.api.addr_canonicalize(info.sender.as_str())? {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    read_schedule_change(deps.storage, id)?;
+    remove_schedule_change(deps.storage, id);
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "cancel_schedule_change"),
+        ("schedule_change_id", id.to_string().as_str()),
+    ]))
+}
+
+pub fn migrate_staking(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    new_staking_contract: String,
+) -> StdResult<Response> {
+    let sender_addr_raw: CanonicalAddr = deps.api.addr_canonicalize(info.sender.as_str())?;
+    let mut config: Config = read_config(deps.storage)?;
+
+    if sender_addr_raw != config.governance {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+    assert_not_paused(deps.storage, Operation::MigrateStaking)?;
+    assert_not_migrated(&config)?;
+    config.migrated_to = Some(deps.api.addr_canonicalize(&new_staking_contract)?);
+
+    let mut state: State = read_state(deps.storage)?;
+
+    // compute global reward, sets last_distributed_seconds to env.block.time.seconds
+    compute_reward(&config, &mut state, env.block.time.seconds());
+
+    let block_time = env.block.time.seconds();
+
+    let mut messages: Vec<CosmosMsg> = vec![];
+    let mut attributes = vec![("action", "migrate_staking".to_string())];
+    for reward in config.rewards.iter_mut() {
+        let total_distribution_amount: Uint128 =
+            reward.all_slots().iter().map(|item| item.amount).sum();
+        let reward_info = reward.info.to_normal(deps.api)?;
+
+        // funders get back what their slots have not released, as on eviction
+        let mut refunded_amount = Uint128::zero();
+        for funded in reward.funded_slots.iter() {
+            let refund_amount = funded
+                .slot
+                .amount
+                .checked_sub(compute_slot_distributed_amount(&funded.slot, block_time))?;
+            if !refund_amount.is_zero() {
+                messages.push(transfer_reward_msg(
+                    deps.as_ref(),
+                    &reward_info,
+                    deps.api.addr_humanize(&funded.funder)?.to_string(),
+                    refund_amount,
+                )?);
+                refunded_amount += refund_amount;
+            }
+        }
+
+        // eliminate distribution slots that have not started
+        reward
+            .distribution_schedule
+            .retain(|slot| slot.start_time < block_time);
+        reward
+            .funded_slots
+            .retain(|funded| funded.slot.start_time < block_time);
+
+        let mut distributed_amount = Uint128::zero();
+        for s in reward.distribution_schedule.iter_mut().chain(
+            reward
+                .funded_slots
+                .iter_mut()
+                .map(|funded| &mut funded.slot),
+        ) {
+            if s.end_time < block_time {
+                // all distributed
+                distributed_amount += s.amount;
+            } else {
+                // partially distributed slot
+                let distributed_amount_on_slot = compute_slot_distributed_amount(s, block_time);
+                distributed_amount += distributed_amount_on_slot;
+
+                // modify distribution slot
+                s.end_time = block_time;
+                s.amount = distributed_amount_on_slot;
+            }
+        }
+
+        let remaining_amount =
+            total_distribution_amount.checked_sub(distributed_amount + refunded_amount)?;
+
+        if !remaining_amount.is_zero() {
+            messages.push(transfer_reward_msg(
+                deps.as_ref(),
+                &reward_info,
+                new_staking_contract.clone(),
+                remaining_amount,
+            )?);
+        }
+
+        attributes.push(("reward_token", reward_info.to_string()));
+        attributes.push(("distributed_amount", distributed_amount.to_string()));
+        attributes.push(("remaining_amount", remaining_amount.to_string()));
+        attributes.push(("refunded_amount", refunded_amount.to_string()));
+    }
+
+    // update config
+    store_config(deps.storage, &config)?;
+    // update state
+    store_state(deps.storage, &state)?;
+
+    Ok(Response::new()
+        .add_messages(messages)
+        .add_attributes(attributes))
+}
+
+// native rewards are sent with the terra tax deducted from the amount
+pub fn migrate_position(deps: DepsMut, env: Env, info: MessageInfo) -> StdResult<Response> {
+    assert_not_paused(deps.storage, Operation::MigrateStaking)?;
+    let config: Config = read_config(deps.storage)?;
+    let new_staking_contract = match &config.migrated_to {
+        Some(migrated_to) => deps.api.addr_humanize(migrated_to)?,
+        None => {
+            return Err(StdError::generic_err(
+                "staking contract has not been migrated",
+            ))
+        }
+    };
+
+    let staker_raw = deps.api.addr_canonicalize(info.sender.as_str())?;
+    let mut state: State = read_state(deps.storage)?;
+    let mut staker_info = read_staker_info(deps.storage, &staker_raw)?;
+    if staker_info.bond_amount.is_zero() {
+        return Err(StdError::generic_err("Nothing bonded"));
+    }
+
+    compute_reward(&config, &mut state, env.block.time.seconds());
+    let amount = bond_tokens(&state, staker_info.bond_amount);
+    let messages = move_position_msgs(
+        deps.as_ref(),
+        &config,
+        &mut state,
+        &new_staking_contract,
+        &info.sender,
+        &mut staker_info,
+    )?;
+
+    remove_staker_info(deps.storage, &staker_raw);
+    store_state(deps.storage, &state)?;
+
+    Ok(Response::new().add_messages(messages).add_attributes(vec![
+        ("action", "migrate_position"),
+        ("staker", info.sender.as_str()),
+        ("amount", amount.to_string().as_str()),
+    ]))
+}
+
+// migrated stakers leave the bucket, so repeated calls walk through all of them
+pub fn migrate_positions(deps: DepsMut, env: Env, limit: Option<u32>) -> StdResult<Response> {
+    assert_not_paused(deps.storage, Operation::MigrateStaking)?;
+    let config: Config = read_config(deps.storage)?;
+    let new_staking_contract = match &config.migrated_to {
+        Some(migrated_to) => deps.api.addr_humanize(migrated_to)?,
+        None => {
+            return Err(StdError::generic_err(
+                "staking contract has not been migrated",
+            ))
+        }
+    };
+
+    let mut state: State = read_state(deps.storage)?;
+    compute_reward(&config, &mut state, env.block.time.seconds());
+
+    let mut messages: Vec<CosmosMsg> = vec![];
+    let staker_infos = read_staker_infos(deps.storage, None, limit)?;
+    for (staker_raw, mut staker_info) in staker_infos.iter().cloned() {
+        let staker = deps.api.addr_humanize(&staker_raw)?;
+        messages.extend(move_position_msgs(
+            deps.as_ref(),
+            &config,
+            &mut state,
+            &new_staking_contract,
+            &staker,
+            &mut staker_info,
+        )?);
+        remove_staker_info(deps.storage, &staker_raw);
+    }
+    store_state(deps.storage, &state)?;
+
+    Ok(Response::new().add_messages(messages).add_attributes(vec![
+        ("action", "migrate_positions"),
+        ("count", staker_infos.len().to_string().as_str()),
+    ]))
+}
+
+// settle the staker and bond its position, with the staking token reward,
+// in the new contract; state must be computed up to the block time
+fn move_position_msgs(
+    deps: Deps,
+    config: &Config,
+    state: &mut State,
+    new_staking_contract: &Addr,
+    staker: &Addr,
+    staker_info: &mut StakerInfo,
+) -> StdResult<Vec<CosmosMsg>> {
+    compute_staker_reward(state, staker_info)?;
+
+    let shares = staker_info.bond_amount;
+    let mut amount = bond_tokens(state, shares);
+    decrease_bond_amount(state, staker_info, shares)?;
+
+    let mut messages: Vec<CosmosMsg> = vec![];
+    let compounding_index = compounding_reward_index(config);
+    for (index, ((reward, pending_reward), unclaimed_reward)) in config
+        .rewards
+        .iter()
+        .zip(staker_info.pending_rewards.iter_mut())
+        .zip(state.unclaimed_rewards.iter_mut())
+        .enumerate()
+    {
+        if pending_reward.is_zero() {
+            continue;
+        }
+
+        *unclaimed_reward = unclaimed_reward.saturating_sub(*pending_reward);
+        if compounding_index == Some(index) {
+            amount += *pending_reward;
+        } else {
+            messages.push(transfer_reward_msg(
+                deps,
+                &reward.info.to_normal(deps.api)?,
+                staker.to_string(),
+                *pending_reward,
+            )?);
+        }
+        *pending_reward = Uint128::zero();
+    }
+
+    if !amount.is_zero() {
+        messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: deps.api.addr_humanize(&config.staking_token)?.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Send {
+                contract: new_staking_contract.to_string(),
+                amount,
+                msg: to_binary(&Cw20HookMsg::Bond {
+                    lock_duration: None,
+                    beneficiary: Some(staker.to_string()),
+                })?,
+            })?,
+            funds: vec![],
+        }));
+    }
+
+    Ok(messages)
+}
+
+fn transfer_reward_msg(
+    deps: Deps,
+    reward_info: &AssetInfo,
+    recipient: String,
+    amount: Uint128,
+) -> StdResult<CosmosMsg> {
+    match reward_info {
+        AssetInfo::Token { contract_addr } => Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: contract_addr.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer { recipient, amount })?,
+            funds: vec![],
+        })),
+        AssetInfo::NativeToken { denom } => Ok(CosmosMsg::Bank(BankMsg::Send {
+            to_address: recipient,
+            amount: vec![deduct_tax(
+                deps,
+                Coin {
+                    denom: denom.to_string(),
+                    amount,
+                },
+            )?],
+        })),
+    }
+}
+
+fn increase_bond_amount(
+    state: &mut State,
+    staker_info: &mut StakerInfo,
+    amount: Uint128,
+) -> StdResult<()> {
+    let weight = staker_weight(staker_info);
+    state.total_bond_amount += amount;
+    staker_info.bond_amount += amount;
+    state.total_weight = state.total_weight.checked_sub(weight)? + staker_weight(staker_info);
+    Ok(())
+}
+
+fn decrease_bond_amount(
+    state: &mut State,
+    staker_info: &mut StakerInfo,
+    amount: Uint128,
+) -> StdResult<()> {
+    let weight = staker_weight(staker_info);
+    state.total_bond_amount = state.total_bond_amount.checked_sub(amount)?;
+    staker_info.bond_amount = staker_info.bond_amount.checked_sub(amount)?;
+    state.total_weight = state.total_weight.checked_sub(weight)? + staker_weight(staker_info);
+    Ok(())
+}
+
+fn update_boost(state: &mut State, staker_info: &mut StakerInfo, boost: Decimal) -> StdResult<()> {
+    let weight = staker_weight(staker_info);
+    staker_info.boost = boost;
+    state.total_weight = state.total_weight.checked_sub(weight)? + staker_weight(staker_info);
+    Ok(())
+}
+
+// boost stays in place until the staker or anyone else touches an ended lock
+fn release_expired_lock(
+    state: &mut State,
+    staker_info: &mut StakerInfo,
+    block_time: u64,
+) -> StdResult<()> {
+    if staker_info.lock_end != 0 && staker_info.lock_end <= block_time {
+        staker_info.lock_end = 0;
+        update_boost(state, staker_info, Decimal::one())?;
+    }
+    Ok(())
+}
+
+// position of the reward asset paid in the staking token itself
+fn compounding_reward_index(config: &Config) -> Option<usize> {
+    config.rewards.iter().position(|reward| {
+        reward.info.equal(&AssetInfoRaw::Token {
+            contract_addr: config.staking_token.clone(),
+        })
+    })
+}
+
+// move the staking token reward into the bond; no tokens leave the contract
+fn compound_reward(
+    config: &Config,
+    state: &mut State,
+    staker_info: &mut StakerInfo,
+) -> StdResult<Uint128> {
+    let index = compounding_reward_index(config).ok_or_else(|| {
+        StdError::generic_err("cannot compound; no reward asset matches the staking token")
+    })?;
+
+    let amount = staker_info
+        .pending_rewards
+        .get_mut(index)
+        .map(std::mem::take)
+        .unwrap_or_default();
+    state.unclaimed_rewards[index] = state.unclaimed_rewards[index].saturating_sub(amount);
+    let shares = bond_shares(state, amount);
+    increase_bond_amount(state, staker_info, shares)?;
+    Ok(amount)
+}
+
+fn auto_compound_reward(
+    config: &Config,
+    state: &mut State,
+    staker_info: &mut StakerInfo,
+) -> StdResult<()> {
+    if staker_info.auto_compound {
+        compound_reward(config, state, staker_info)?;
+    }
+    Ok(())
+}
+
+// bond shares bought by `amount` staking tokens, rounded down
+fn bond_shares(state: &State, amount: Uint128) -> Uint128 {
+    (Uint256::from(amount) / state.share_price).into()
+}
+
+// bond shares to give up for `amount` staking tokens, rounded up
+fn unbond_shares(state: &State, amount: Uint128) -> Uint128 {
+    let shares = bond_shares(state, amount);
+    if bond_tokens(state, shares) < amount {
+        shares + Uint128::from(1u128)
+    } else {
+        shares
+    }
+}
+
+// staking tokens the bond shares are worth, rounded down
+fn bond_tokens(state: &State, shares: Uint128) -> Uint128 {
+    (Uint256::from(shares) * state.share_price).into()
+}
+
+fn staker_weight(staker_info: &StakerInfo) -> Uint128 {
+    staker_info.bond_amount * staker_info.boost
+}
+
+// highest boost step the lock duration reaches
+fn compute_boost(boost_curve: &[(u64, Decimal)], lock_duration: u64) -> StdResult<Decimal> {
+    match boost_curve.last() {
+        Some(longest) if lock_duration <= longest.0 => Ok(boost_curve
+            .iter()
+            .rev()
+            .find(|step| step.0 <= lock_duration)
+            .map(|step| step.1)
+            .unwrap_or_else(Decimal::one)),
+        _ => Err(StdError::generic_err(
+            "lock duration exceeds the longest lock of the boost curve",
+        )),
+    }
+}
+
+// compute distributed rewards and update global reward indexes
+fn compute_reward(config: &Config, state: &mut State, block_time: u64) {
+    // reward assets appended since the last distribution start from a zero index
+    state
+        .global_reward_indexes
+        .resize(config.rewards.len(), Decimal256::zero());
+    state
+        .reward_dust
+        .resize(config.rewards.len(), Decimal256::zero());
+    state
+        .unclaimed_rewards
+        .resize(config.rewards.len(), Uint128::zero());
+
+    if state.total_weight.is_zero() {
+        state.last_distributed = block_time;
+        return;
+    }
+
+    let total_weight = Decimal256::from_uint256(state.total_weight);
+    for (((reward, global_reward_index), reward_dust), unclaimed_reward) in config
+        .rewards
+        .iter()
+        .zip(state.global_reward_indexes.iter_mut())
+        .zip(state.reward_dust.iter_mut())
+        .zip(state.unclaimed_rewards.iter_mut())
+    {
+        let distributed_amount =
+            compute_distributed_amount(&reward.all_slots(), state.last_distributed, block_time);
+
+        // whatever the index rounds away is kept for the next distribution
+        let amount = Decimal256::from_uint256(distributed_amount) + *reward_dust;
+        let index_increase = amount / total_weight;
+        *global_reward_index += index_increase;
+        *reward_dust = amount - index_increase * total_weight;
+        *unclaimed_reward += distributed_amount;
+    }
+
+    state.last_distributed = block_time;
+}
+
+// amount a slot releases every second while it runs
+fn compute_slot_emission_rate(slot: &DistributionSchedule) -> Decimal256 {
+    Decimal256::from_ratio(
+        Uint256::from(slot.amount),
+        Uint256::from(slot.end_time - slot.start_time),
+    )
+}
+
+// amount a slot has released by block_time; measured from the slot start,
+// so the rounding never adds up and the whole amount is out by the end
+fn compute_slot_distributed_amount(slot: &DistributionSchedule, block_time: u64) -> Uint128 {
+    if block_time <= slot.start_time {
+        return Uint128::zero();
+    }
+
+    let passed_time = std::cmp::min(block_time, slot.end_time) - slot.start_time;
+    slot.amount
+        .multiply_ratio(passed_time, slot.end_time - slot.start_time)
+}
+
+// compute the amount a distribution schedule releases between last_distributed and block_time
+fn compute_distributed_amount(
+    distribution_schedule: &[DistributionSchedule],
+    last_distributed: u64,
+    block_time: u64,
+) -> Uint128 {
+    let mut distributed_amount: Uint128 = Uint128::zero();
+    for s in distribution_schedule.iter() {
+        if s.start_time > block_time || s.end_time < last_distributed {
+            continue;
+        }
+
+        distributed_amount += compute_slot_distributed_amount(s, block_time)
+            - compute_slot_distributed_amount(s, last_distributed);
+    }
+
+    distributed_amount
+}
+
+// withdraw reward to pending reward
+fn compute_staker_reward(state: &State, staker_info: &mut StakerInfo) -> StdResult<()> {
+    // reward assets appended after the staker's last update start from a zero index
+    let reward_count = state.global_reward_indexes.len();
+    staker_info
+        .reward_indexes
+        .resize(reward_count, Decimal256::zero());
+    staker_info
+        .pending_rewards
+        .resize(reward_count, Uint128::zero());
+
+    let weight = Uint256::from(staker_weight(staker_info));
+    for ((global_reward_index, reward_index), pending_reward) in state
+        .global_reward_indexes
+        .iter()
+        .zip(staker_info.reward_indexes.iter_mut())
+        .zip(staker_info.pending_rewards.iter_mut())
+    {
+        *pending_reward += Uint128::from(weight * *global_reward_index - weight * *reward_index);
+        *reward_index = *global_reward_index;
+    }
+
+    Ok(())
+}
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::Config {} => to_binary(&query_config(deps)?),
         QueryMsg::State { block_time } => to_binary(&query_state(deps, block_time)?),
         QueryMsg::StakerInfo { staker, block_time } => {
             to_binary(&query_staker_info(deps, staker, block_time)?)
         }
+        QueryMsg::Unbondings { staker } => to_binary(&query_unbondings(deps, env, staker)?),
+        QueryMsg::AllStakers {
+            start_after,
+            limit,
+            block_time,
+        } => to_binary(&query_all_stakers(deps, start_after, limit, block_time)?),
+        QueryMsg::WithdrawPermissions { staker } => {
+            to_binary(&query_withdraw_permissions(deps, staker)?)
+        }
+        QueryMsg::PauseStatus {} => to_binary(&query_pause_status(deps)?),
+        QueryMsg::Solvency {} => to_binary(&query_solvency(deps, env)?),
+        QueryMsg::PendingGovernance {} => to_binary(&query_pending_governance(deps)?),
+        QueryMsg::RewardRate { block_time } => {
+            to_binary(&query_reward_rate(deps, env, block_time)?)
+        }
     }
 }
 
 pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
     let state = read_config(deps.storage)?;
+    let mut funded_slots = vec![];
+    for reward in state.rewards.iter() {
+        let reward_token = reward.info.to_normal(deps.api)?.to_string();
+        for funded in reward.funded_slots.iter() {
+            funded_slots.push(FundedSlotResponse {
+                reward_token: reward_token.clone(),
+                funder: deps.api.addr_humanize(&funded.funder)?.to_string(),
+                slot: funded.slot.clone(),
+            });
+        }
+    }
     let resp = ConfigResponse {
-        reward_token: deps.api.addr_humanize(&state.reward_token)?.to_string(),
         staking_token: deps.api.addr_humanize(&state.staking_token)?.to_string(),
-        distribution_schedule: state.distribution_schedule,
+        rewards: state
+            .rewards
+            .iter()
+            .map(|reward| reward.to_normal(deps.api))
+            .collect::<StdResult<Vec<RewardAsset>>>()?,
         governance: deps.api.addr_humanize(&state.governance)?.to_string(),
+        guardian: match state.guardian {
+            Some(guardian) => Some(deps.api.addr_humanize(&guardian)?.to_string()),
+            None => None,
+        },
+        unbonding_period: state.unbonding_period,
+        boost_curve: state.boost_curve,
+        reject_underfunded_schedules: state.reject_underfunded_schedules,
+        pending_governance: match state.pending_governance {
+            Some(pending_governance) => {
+                Some(deps.api.addr_humanize(&pending_governance)?.to_string())
+            }
+            None => None,
+        },
+        schedule_timelock: state.schedule_timelock,
+        migrated_to: match state.migrated_to {
+            Some(migrated_to) => Some(deps.api.addr_humanize(&migrated_to)?.to_string()),
+            None => None,
+        },
+        funded_slots,
     };
 
     Ok(resp)
 }
 
 pub fn query_state(deps: Deps, block_time: Option<u64>) -> StdResult<StateResponse> {
+    let config = read_config(deps.storage)?;
     let mut state: State = read_state(deps.storage)?;
     if let Some(block_time) = block_time {
-        let config = read_config(deps.storage)?;
         compute_reward(&config, &mut state, block_time);
     }
 
+    let mut global_reward_indexes = vec![];
+    for (i, reward) in config.rewards.iter().enumerate() {
+        global_reward_indexes.push((
+            reward.info.to_normal(deps.api)?.to_string(),
+            state
+                .global_reward_indexes
+                .get(i)
+                .cloned()
+                .unwrap_or_else(Decimal256::zero),
+        ));
+    }
+
     Ok(StateResponse {
         last_distributed: state.last_distributed,
-        total_bond_amount: state.total_bond_amount,
-        global_reward_index: state.global_reward_index,
+        total_bond_amount: bond_tokens(&state, state.total_bond_amount),
+        total_weight: state.total_weight,
+        global_reward_indexes,
+        share_price: state.share_price,
     })
 }
 
@@ -397,41 +2042,394 @@ pub fn query_staker_info(
 ) -> StdResult<StakerInfoResponse> {
     let staker_raw = deps.api.addr_canonicalize(&staker)?;
 
+    let config = read_config(deps.storage)?;
+    let mut state = read_state(deps.storage)?;
     let mut staker_info: StakerInfo = read_staker_info(deps.storage, &staker_raw)?;
     if let Some(block_time) = block_time {
-        let config = read_config(deps.storage)?;
-        let mut state = read_state(deps.storage)?;
-
         compute_reward(&config, &mut state, block_time);
         compute_staker_reward(&state, &mut staker_info)?;
     }
 
+    to_staker_info_response(deps, &config, &state, staker, staker_info)
+}
+
+// reward assets the staker has not been settled against yet are reported as zero
+fn to_staker_info_response(
+    deps: Deps,
+    config: &Config,
+    state: &State,
+    staker: String,
+    staker_info: StakerInfo,
+) -> StdResult<StakerInfoResponse> {
+    let mut reward_indexes = vec![];
+    let mut pending_rewards = vec![];
+    for (i, reward) in config.rewards.iter().enumerate() {
+        let reward_token = reward.info.to_normal(deps.api)?.to_string();
+        reward_indexes.push((
+            reward_token.clone(),
+            staker_info
+                .reward_indexes
+                .get(i)
+                .cloned()
+                .unwrap_or_else(Decimal256::zero),
+        ));
+        pending_rewards.push((
+            reward_token,
+            staker_info
+                .pending_rewards
+                .get(i)
+                .cloned()
+                .unwrap_or_else(Uint128::zero),
+        ));
+    }
+
     Ok(StakerInfoResponse {
         staker,
-        reward_index: staker_info.reward_index,
-        bond_amount: staker_info.bond_amount,
-        pending_reward: staker_info.pending_reward,
+        reward_indexes,
+        bond_amount: bond_tokens(state, staker_info.bond_amount),
+        pending_rewards,
+        boost: staker_info.boost,
+        lock_end: staker_info.lock_end,
+        auto_compound: staker_info.auto_compound,
     })
 }
 
-pub fn assert_new_schedules(
+pub fn query_all_stakers(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+    block_time: Option<u64>,
+) -> StdResult<AllStakersResponse> {
+    let start_after = if let Some(start_after) = start_after {
+        Some(deps.api.addr_canonicalize(&start_after)?)
+    } else {
+        None
+    };
+
+    let config = read_config(deps.storage)?;
+    let mut state = read_state(deps.storage)?;
+    if let Some(block_time) = block_time {
+        compute_reward(&config, &mut state, block_time);
+    }
+
+    let stakers = read_staker_infos(deps.storage, start_after, limit)?
+        .into_iter()
+        .map(|(staker_raw, mut staker_info)| {
+            if block_time.is_some() {
+                compute_staker_reward(&state, &mut staker_info)?;
+            }
+
+            let staker = deps.api.addr_humanize(&staker_raw)?.to_string();
+            to_staker_info_response(deps, &config, &state, staker, staker_info)
+        })
+        .collect::<StdResult<Vec<StakerInfoResponse>>>()?;
+
+    Ok(AllStakersResponse { stakers })
+}
+
+pub fn query_unbondings(deps: Deps, env: Env, staker: String) -> StdResult<UnbondingsResponse> {
+    let staker_raw = deps.api.addr_canonicalize(&staker)?;
+
+    let block_time = env.block.time.seconds();
+    let unbondings: Vec<UnbondingResponse> = read_unbondings(deps.storage, &staker_raw)?
+        .into_iter()
+        .map(|unbonding| UnbondingResponse {
+            amount: unbonding.amount,
+            release_time: unbonding.release_time,
+            matured: unbonding.release_time <= block_time,
+        })
+        .collect();
+
+    Ok(UnbondingsResponse { staker, unbondings })
+}
+
+pub fn query_withdraw_permissions(
+    deps: Deps,
+    staker: String,
+) -> StdResult<WithdrawPermissionsResponse> {
+    let staker_raw = deps.api.addr_canonicalize(&staker)?;
+    let permissions = read_withdraw_permissions(deps.storage, &staker_raw)?;
+
+    let operators = permissions
+        .operators
+        .iter()
+        .map(|addr| Ok(deps.api.addr_humanize(addr)?.to_string()))
+        .collect::<StdResult<Vec<String>>>()?;
+    let recipients = permissions
+        .recipients
+        .iter()
+        .map(|addr| Ok(deps.api.addr_humanize(addr)?.to_string()))
+        .collect::<StdResult<Vec<String>>>()?;
+
+    Ok(WithdrawPermissionsResponse {
+        staker,
+        operators,
+        recipients,
+    })
+}
+
+pub fn query_solvency(deps: Deps, env: Env) -> StdResult<SolvencyResponse> {
+    let config = read_config(deps.storage)?;
+    let mut state = read_state(deps.storage)?;
+    compute_reward(&config, &mut state, env.block.time.seconds());
+
+    let rewards = (0..config.rewards.len())
+        .map(|index| compute_reward_solvency(deps, &env, &config, &state, index))
+        .collect::<StdResult<Vec<RewardSolvencyResponse>>>()?;
+
+    Ok(SolvencyResponse { rewards })
+}
+
+pub fn query_reward_rate(
+    deps: Deps,
+    env: Env,
+    block_time: Option<u64>,
+) -> StdResult<RewardRateResponse> {
+    let config = read_config(deps.storage)?;
+    let state = read_state(deps.storage)?;
+    let block_time = block_time.unwrap_or_else(|| env.block.time.seconds());
+    let compounding_index = compounding_reward_index(&config);
+
+    let mut rewards = vec![];
+    for (index, reward) in config.rewards.iter().enumerate() {
+        let mut emission_per_second = Decimal256::zero();
+        let mut yearly_emission = Uint256::zero();
+        let mut next_change_time: Option<u64> = None;
+        let slots = reward.all_slots();
+        for slot in slots.iter() {
+            if slot.start_time <= block_time && block_time < slot.end_time {
+                let rate = compute_slot_emission_rate(slot);
+                emission_per_second += rate;
+                yearly_emission += Uint256::from(SECONDS_PER_YEAR) * rate;
+            }
+
+            let change_time = if slot.start_time > block_time {
+                slot.start_time
+            } else {
+                slot.end_time
+            };
+            if change_time > block_time {
+                next_change_time =
+                    Some(next_change_time.map_or(change_time, |t| t.min(change_time)));
+            }
+        }
+
+        let apr = if compounding_index == Some(index) && !state.total_bond_amount.is_zero() {
+            Some(
+                Decimal256::from_uint256(yearly_emission)
+                    / Decimal256::from_uint256(bond_tokens(&state, state.total_bond_amount)),
+            )
+        } else {
+            None
+        };
+
+        rewards.push(RewardAssetRateResponse {
+            reward_token: reward.info.to_normal(deps.api)?.to_string(),
+            emission_per_second,
+            undistributed_amount: compute_distributed_amount(&slots, block_time, u64::MAX),
+            next_change_time,
+            apr,
+        });
+    }
+
+    Ok(RewardRateResponse { rewards })
+}
+
+pub fn query_pending_governance(deps: Deps) -> StdResult<PendingGovernanceResponse> {
+    let config = read_config(deps.storage)?;
+    let schedule_changes = read_schedule_changes(deps.storage)?
+        .into_iter()
+        .map(|(id, queued_change)| ScheduleChangeResponse {
+            id,
+            change: queued_change.change,
+            eta: queued_change.eta,
+        })
+        .collect();
+
+    Ok(PendingGovernanceResponse {
+        governance: deps.api.addr_humanize(&config.governance)?.to_string(),
+        pending_governance: match config.pending_governance {
+            Some(pending_governance) => {
+                Some(deps.api.addr_humanize(&pending_governance)?.to_string())
+            }
+            None => None,
+        },
+        schedule_timelock: config.schedule_timelock,
+        schedule_changes,
+    })
+}
+
+pub fn query_pause_status(deps: Deps) -> StdResult<PauseStatusResponse> {
+    let pause_info = read_pause_info(deps.storage)?;
+    Ok(PauseStatusResponse {
+        bond: pause_info.bond,
+        unbond: pause_info.unbond,
+        withdraw: pause_info.withdraw,
+        migrate_staking: pause_info.migrate_staking,
+        emergency_unbond: pause_info.emergency_unbond,
+    })
+}
+
+pub fn assert_not_migrated(config: &Config) -> StdResult<()> {
+    if config.migrated_to.is_some() {
+        return Err(StdError::generic_err("staking contract has been migrated"));
+    }
+    Ok(())
+}
+
+pub fn assert_not_paused(storage: &dyn Storage, operation: Operation) -> StdResult<()> {
+    if read_pause_info(storage)?.is_paused(&operation) {
+        return Err(StdError::generic_err("operation is paused"));
+    }
+    Ok(())
+}
+
+fn reward_asset_index(deps: Deps, config: &Config, reward: &AssetInfo) -> StdResult<usize> {
+    let reward_raw = reward.to_raw(deps.api)?;
+    config
+        .rewards
+        .iter()
+        .position(|item| item.info.equal(&reward_raw))
+        .ok_or_else(|| StdError::generic_err("unknown reward asset"))
+}
+
+fn query_reward_balance(deps: Deps, env: &Env, reward: &AssetInfo) -> StdResult<Uint128> {
+    match reward {
+        AssetInfo::Token { contract_addr } => query_token_balance(
+            deps,
+            deps.api.addr_validate(contract_addr)?,
+            env.contract.address.clone(),
+        ),
+        AssetInfo::NativeToken { denom } => {
+            query_balance(deps, env.contract.address.clone(), denom.to_string())
+        }
+    }
+}
+
+// compare the reward balance with what is left to distribute and what
+// stakers have not withdrawn; state must be computed up to the block time
+fn compute_reward_solvency(
+    deps: Deps,
+    env: &Env,
+    config: &Config,
+    state: &State,
+    index: usize,
+) -> StdResult<RewardSolvencyResponse> {
+    let reward = &config.rewards[index];
+    let reward_info = reward.info.to_normal(deps.api)?;
+
+    let balance = query_reward_balance(deps, env, &reward_info)?;
+    let principal_amount = if compounding_reward_index(config) == Some(index) {
+        bond_tokens(state, state.total_bond_amount) + state.total_unbonding_amount
+    } else {
+        Uint128::zero()
+    };
+    let undistributed_amount =
+        compute_distributed_amount(&reward.all_slots(), state.last_distributed, u64::MAX);
+    let unclaimed_amount = state
+        .unclaimed_rewards
+        .get(index)
+        .cloned()
+        .unwrap_or_default();
+
+    Ok(RewardSolvencyResponse {
+        reward_token: reward_info.to_string(),
+        balance,
+        principal_amount,
+        undistributed_amount,
+        unclaimed_amount,
+        solvent: balance >= principal_amount + undistributed_amount + unclaimed_amount,
+    })
+}
+
+// check every reward asset against its schedule as of the block time
+fn assert_rewards_funded(deps: Deps, env: &Env, config: &Config) -> StdResult<()> {
+    let mut state: State = read_state(deps.storage)?;
+    compute_reward(config, &mut state, env.block.time.seconds());
+    for index in 0..config.rewards.len() {
+        assert_reward_funded(deps, env, config, &state, index)?;
+    }
+    Ok(())
+}
+
+pub fn assert_reward_funded(
+    deps: Deps,
+    env: &Env,
     config: &Config,
     state: &State,
-    distribution_schedule: Vec<(u64, u64, Uint128)>,
+    index: usize,
+) -> StdResult<()> {
+    if !compute_reward_solvency(deps, env, config, state, index)?.solvent {
+        return Err(StdError::generic_err(
+            "reward balance does not cover the distribution schedule",
+        ));
+    }
+    Ok(())
+}
+
+pub fn assert_unique_reward_tokens(rewards: &[RewardAssetRaw]) -> StdResult<()> {
+    for (i, reward) in rewards.iter().enumerate() {
+        if rewards[..i]
+            .iter()
+            .any(|other| other.info.equal(&reward.info))
+        {
+            return Err(StdError::generic_err("duplicate reward token"));
+        }
+    }
+    Ok(())
+}
+
+// lock durations must increase along the curve and boosts never fall below one
+pub fn assert_boost_curve(boost_curve: &[(u64, Decimal)]) -> StdResult<()> {
+    let mut previous: Option<&(u64, Decimal)> = None;
+    for step in boost_curve.iter() {
+        if step.1 < Decimal::one() {
+            return Err(StdError::generic_err("boost must not be lower than one"));
+        }
+        if let Some(previous) = previous {
+            if step.0 <= previous.0 || step.1 < previous.1 {
+                return Err(StdError::generic_err(
+                    "boost curve must be ordered by increasing lock duration and boost",
+                ));
+            }
+        }
+        previous = Some(step);
+    }
+    Ok(())
+}
+
+// every slot must release its amount over a non-empty period
+pub fn assert_distribution_schedule(
+    distribution_schedule: &[DistributionSchedule],
+) -> StdResult<()> {
+    for schedule in distribution_schedule.iter() {
+        if schedule.start_time >= schedule.end_time {
+            return Err(StdError::generic_err(
+                "distribution schedule must end after it starts",
+            ));
+        }
+    }
+    Ok(())
+}
+
+pub fn assert_new_schedules(
+    current_schedule: &[DistributionSchedule],
+    state: &State,
+    distribution_schedule: Vec<DistributionSchedule>,
 ) -> StdResult<()> {
-    if distribution_schedule.len() < config.distribution_schedule.len() {
+    if distribution_schedule.len() < current_schedule.len() {
         return Err(StdError::generic_err(
             "cannot update; the new schedule must support all of the previous schedule",
         ));
     }
 
-    let mut existing_counts: BTreeMap<(u64, u64, Uint128), u32> = BTreeMap::new();
-    for schedule in config.distribution_schedule.clone() {
+    let mut existing_counts: BTreeMap<DistributionSchedule, u32> = BTreeMap::new();
+    for schedule in current_schedule.iter().cloned() {
         let counter = existing_counts.entry(schedule).or_insert(0);
         *counter += 1;
     }
 
-    let mut new_counts: BTreeMap<(u64, u64, Uint128), u32> = BTreeMap::new();
+    let mut new_counts: BTreeMap<DistributionSchedule, u32> = BTreeMap::new();
     for schedule in distribution_schedule.clone() {
         let counter = new_counts.entry(schedule).or_insert(0);
         *counter += 1;
@@ -439,7 +2437,7 @@ pub fn assert_new_schedules(
 
     for (schedule, count) in existing_counts.into_iter() {
         // if began ensure its in the new schedule
-        if schedule.0 <= state.last_distributed {
+        if schedule.start_time <= state.last_distributed {
             if count > *new_counts.get(&schedule).unwrap_or(&0u32) {
                 return Err(StdError::generic_err(
                     "new schedule removes already started distribution",
@@ -451,7 +2449,7 @@ pub fn assert_new_schedules(
     }
 
     for (schedule, count) in new_counts.into_iter() {
-        if count > 0 && schedule.0 <= state.last_distributed {
+        if count > 0 && schedule.start_time <= state.last_distributed {
             return Err(StdError::generic_err(
                 "new schedule adds an already started distribution",
             ));
@@ -461,6 +2459,33 @@ pub fn assert_new_schedules(
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> StdResult<Response> {
-    Ok(Response::default())
+pub fn migrate(deps: DepsMut, env: Env, msg: MigrateMsg) -> StdResult<Response> {
+    // deployments from before version tracking count as 0.0.0
+    let stored_version = match CONTRACT.may_load(deps.storage)? {
+        Some(contract_version) => {
+            if contract_version.contract != CONTRACT_NAME {
+                return Err(StdError::generic_err(format!(
+                    "cannot migrate from contract {}",
+                    contract_version.contract
+                )));
+            }
+            contract_version.version
+        }
+        None => "0.0.0".to_string(),
+    };
+    if parse_version(&stored_version)? > parse_version(CONTRACT_VERSION)? {
+        return Err(StdError::generic_err(format!(
+            "cannot migrate from newer version {}",
+            stored_version
+        )));
+    }
+
+    run_migrations(deps.storage, &env, &msg, &stored_version)?;
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "migrate"),
+        ("from_version", stored_version.as_str()),
+        ("to_version", CONTRACT_VERSION),
+    ]))
 }
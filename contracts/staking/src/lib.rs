@@ -1,5 +1,9 @@
 pub mod contract;
+pub mod migration;
 pub mod state;
 
+#[cfg(test)]
+mod mock_querier;
+
 #[cfg(test)]
 mod testing;
@@ -0,0 +1,239 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_bignumber::Decimal256;
+use cosmwasm_std::{CanonicalAddr, Decimal, Env, Order, StdError, StdResult, Storage, Uint128};
+use cosmwasm_storage::{singleton, singleton_read, Bucket, ReadonlyBucket};
+
+use gohm_staking::asset::AssetInfoRaw;
+use gohm_staking::staking::{DistributionSchedule, MigrateMsg};
+
+use crate::state::{
+    store_config, store_state, Config, RewardAssetRaw, StakerInfo, State, UnbondingInfo,
+    KEY_CONFIG, KEY_STATE, PREFIX_REWARD, PREFIX_UNBONDING,
+};
+
+type MigrationStep = fn(&mut dyn Storage, &Env, &MigrateMsg) -> StdResult<()>;
+
+/// Storage migrations in order, with the version whose layout they produce.
+/// A deployment runs every step newer than its stored version; layouts that
+/// never shipped in a release get no step of their own
+const MIGRATIONS: &[(&str, MigrationStep)] = &[
+    ("0.1.0", migrate_block_schedules),
+    ("0.1.0", migrate_single_reward),
+];
+
+pub fn run_migrations(
+    storage: &mut dyn Storage,
+    env: &Env,
+    msg: &MigrateMsg,
+    from_version: &str,
+) -> StdResult<()> {
+    let from_version = parse_version(from_version)?;
+    for (version, step) in MIGRATIONS.iter() {
+        if parse_version(version)? > from_version {
+            step(storage, env, msg)?;
+        }
+    }
+    Ok(())
+}
+
+/// splits a `major.minor.patch` version for comparison
+pub fn parse_version(version: &str) -> StdResult<(u64, u64, u64)> {
+    let invalid_version = || StdError::generic_err(format!("invalid version {}", version));
+    let parts = version
+        .split('.')
+        .map(|part| part.parse::<u64>())
+        .collect::<Result<Vec<u64>, _>>()
+        .map_err(|_| invalid_version())?;
+    match parts[..] {
+        [major, minor, patch] => Ok((major, minor, patch)),
+        _ => Err(invalid_version()),
+    }
+}
+
+/// config of deployments paying a single reward token
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct LegacyConfig {
+    pub reward_token: CanonicalAddr,
+    pub staking_token: CanonicalAddr,
+    pub distribution_schedule: Vec<(u64, u64, Uint128)>,
+    pub governance: CanonicalAddr,
+    /// not stored by deployments that predate the unbonding period
+    #[serde(default)]
+    pub unbonding_period: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct LegacyState {
+    pub last_distributed: u64,
+    pub total_bond_amount: Uint128,
+    pub global_reward_index: Decimal,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct LegacyStakerInfo {
+    pub reward_index: Decimal,
+    pub bond_amount: Uint128,
+    pub pending_reward: Uint128,
+}
+
+/// Converts the block heights of a single reward deployment to times.
+/// Rewards are settled up to the current height with the block-based
+/// schedule first; heights then map linearly around the current block,
+/// so what is left of a running slot is released over the matching time
+fn migrate_block_schedules(
+    storage: &mut dyn Storage,
+    env: &Env,
+    msg: &MigrateMsg,
+) -> StdResult<()> {
+    let seconds_per_block = match msg.seconds_per_block {
+        Some(seconds_per_block) => seconds_per_block,
+        None => return Ok(()),
+    };
+
+    let mut legacy_config: LegacyConfig = singleton_read(storage, KEY_CONFIG)
+        .load()
+        .map_err(|_| StdError::generic_err("only single reward deployments are block-based"))?;
+    let mut legacy_state: LegacyState = singleton_read(storage, KEY_STATE).load()?;
+
+    let height = env.block.height;
+    if !legacy_state.total_bond_amount.is_zero() {
+        let mut distributed_amount = Uint128::zero();
+        for (start_height, end_height, amount) in legacy_config.distribution_schedule.iter() {
+            if *start_height > height || *end_height < legacy_state.last_distributed {
+                continue;
+            }
+
+            let passed_blocks = std::cmp::min(*end_height, height)
+                - std::cmp::max(*start_height, legacy_state.last_distributed);
+            let distribution_amount_per_block =
+                Decimal::from_ratio(*amount, end_height - start_height);
+            distributed_amount +=
+                distribution_amount_per_block * Uint128::from(passed_blocks as u128);
+        }
+
+        legacy_state.global_reward_index = legacy_state.global_reward_index
+            + Decimal::from_ratio(distributed_amount, legacy_state.total_bond_amount);
+    }
+
+    let now = env.block.time.seconds();
+    let to_time = |block: u64| -> u64 {
+        if block >= height {
+            now + (seconds_per_block * Uint128::from(block - height)).u128() as u64
+        } else {
+            now.saturating_sub((seconds_per_block * Uint128::from(height - block)).u128() as u64)
+        }
+    };
+
+    let mut distribution_schedule = vec![];
+    for (start_height, end_height, amount) in legacy_config.distribution_schedule.iter() {
+        let (start_time, end_time) = (to_time(*start_height), to_time(*end_height));
+        if start_time >= end_time {
+            return Err(StdError::generic_err(
+                "seconds per block too small to convert the distribution schedule",
+            ));
+        }
+        distribution_schedule.push((start_time, end_time, *amount));
+    }
+    legacy_config.distribution_schedule = distribution_schedule;
+    legacy_state.last_distributed = now;
+
+    singleton(storage, KEY_CONFIG).save(&legacy_config)?;
+    singleton(storage, KEY_STATE).save(&legacy_state)
+}
+
+/// converts single reward storage into a one-asset reward list
+fn migrate_single_reward(
+    storage: &mut dyn Storage,
+    _env: &Env,
+    _msg: &MigrateMsg,
+) -> StdResult<()> {
+    let legacy_config: LegacyConfig = singleton_read(storage, KEY_CONFIG).load()?;
+    store_config(
+        storage,
+        &Config {
+            staking_token: legacy_config.staking_token,
+            rewards: vec![RewardAssetRaw {
+                info: AssetInfoRaw::Token {
+                    contract_addr: legacy_config.reward_token,
+                },
+                distribution_schedule: legacy_config
+                    .distribution_schedule
+                    .into_iter()
+                    .map(|(start_time, end_time, amount)| DistributionSchedule {
+                        start_time,
+                        end_time,
+                        amount,
+                    })
+                    .collect(),
+                funded_slots: vec![],
+            }],
+            governance: legacy_config.governance,
+            pending_governance: None,
+            guardian: None,
+            unbonding_period: legacy_config.unbonding_period,
+            boost_curve: vec![],
+            reject_underfunded_schedules: false,
+            schedule_timelock: 0,
+            migrated_to: None,
+        },
+    )?;
+
+    let legacy_staker_infos = ReadonlyBucket::<LegacyStakerInfo>::new(storage, PREFIX_REWARD)
+        .range(None, None, Order::Ascending)
+        .collect::<StdResult<Vec<(Vec<u8>, LegacyStakerInfo)>>>()?;
+
+    // rewards owed to stakers as of the last distribution
+    let legacy_state: LegacyState = singleton_read(storage, KEY_STATE).load()?;
+    let mut unclaimed_reward = Uint128::zero();
+    for (_, legacy_staker_info) in legacy_staker_infos.iter() {
+        unclaimed_reward += legacy_staker_info.pending_reward
+            + (legacy_staker_info.bond_amount * legacy_state.global_reward_index)
+                .checked_sub(legacy_staker_info.bond_amount * legacy_staker_info.reward_index)?;
+    }
+
+    let mut total_unbonding_amount = Uint128::zero();
+    for item in ReadonlyBucket::<Vec<UnbondingInfo>>::new(storage, PREFIX_UNBONDING).range(
+        None,
+        None,
+        Order::Ascending,
+    ) {
+        let (_, unbondings) = item?;
+        total_unbonding_amount += unbondings
+            .iter()
+            .map(|unbonding| unbonding.amount)
+            .sum::<Uint128>();
+    }
+
+    store_state(
+        storage,
+        &State {
+            last_distributed: legacy_state.last_distributed,
+            total_bond_amount: legacy_state.total_bond_amount,
+            total_weight: legacy_state.total_bond_amount,
+            total_unbonding_amount,
+            global_reward_indexes: vec![legacy_state.global_reward_index.into()],
+            reward_dust: vec![Decimal256::zero()],
+            unclaimed_rewards: vec![unclaimed_reward],
+            share_price: Decimal256::one(),
+        },
+    )?;
+
+    let mut bucket = Bucket::<StakerInfo>::new(storage, PREFIX_REWARD);
+    for (key, legacy_staker_info) in legacy_staker_infos {
+        bucket.save(
+            &key,
+            &StakerInfo {
+                reward_indexes: vec![legacy_staker_info.reward_index.into()],
+                bond_amount: legacy_staker_info.bond_amount,
+                pending_rewards: vec![legacy_staker_info.pending_reward],
+                boost: Decimal::one(),
+                lock_end: 0,
+                auto_compound: false,
+            },
+        )?;
+    }
+
+    Ok(())
+}
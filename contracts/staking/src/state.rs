@@ -1,20 +1,84 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use cosmwasm_std::{CanonicalAddr, Decimal, StdResult, Storage, Uint128};
+use cosmwasm_bignumber::Decimal256;
+use cosmwasm_std::{Api, CanonicalAddr, Decimal, Order, StdError, StdResult, Storage, Uint128};
 use cosmwasm_storage::{singleton, singleton_read, Bucket, ReadonlyBucket};
+use gohm_staking::asset::AssetInfoRaw;
+use gohm_staking::staking::{DistributionSchedule, Operation, RewardAsset, ScheduleChange};
 
-static KEY_CONFIG: &[u8] = b"config";
-static KEY_STATE: &[u8] = b"state";
+pub static KEY_CONFIG: &[u8] = b"config";
+pub static KEY_STATE: &[u8] = b"state";
+static KEY_PAUSE: &[u8] = b"pause";
+static KEY_SCHEDULE_CHANGE_COUNT: &[u8] = b"schedule_change_count";
 
-static PREFIX_REWARD: &[u8] = b"reward";
+pub static PREFIX_REWARD: &[u8] = b"reward";
+pub static PREFIX_UNBONDING: &[u8] = b"unbonding";
+static PREFIX_WITHDRAW_PERMISSIONS: &[u8] = b"withdraw_permissions";
+static PREFIX_SCHEDULE_CHANGE: &[u8] = b"schedule_change";
+
+// settings for pagination
+const MAX_LIMIT: u32 = 30;
+const DEFAULT_LIMIT: u32 = 10;
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct Config {
-    pub reward_token: CanonicalAddr,
     pub staking_token: CanonicalAddr,
-    pub distribution_schedule: Vec<(u64, u64, Uint128)>,
+    pub rewards: Vec<RewardAssetRaw>,
     pub governance: CanonicalAddr,
+    /// Nominated governance waiting to accept
+    pub pending_governance: Option<CanonicalAddr>,
+    pub guardian: Option<CanonicalAddr>,
+    pub unbonding_period: u64,
+    pub boost_curve: Vec<(u64, Decimal)>,
+    pub reject_underfunded_schedules: bool,
+    pub schedule_timelock: u64,
+    /// New staking contract; set by MigrateStaking, after which
+    /// no bonds or schedule changes are accepted
+    pub migrated_to: Option<CanonicalAddr>,
+}
+
+/// Reward assets are only ever appended, so their position is used
+/// as the key of the matching indexes in `State` and `StakerInfo`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RewardAssetRaw {
+    pub info: AssetInfoRaw,
+    pub distribution_schedule: Vec<DistributionSchedule>,
+    /// Slots paid through `Fund`, kept apart so governance schedule
+    /// checks never see them
+    pub funded_slots: Vec<FundedSlot>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct FundedSlot {
+    pub funder: CanonicalAddr,
+    pub slot: DistributionSchedule,
+}
+
+impl RewardAssetRaw {
+    pub fn from_normal(api: &dyn Api, reward: &RewardAsset) -> StdResult<Self> {
+        Ok(RewardAssetRaw {
+            info: reward.info.to_raw(api)?,
+            distribution_schedule: reward.distribution_schedule.clone(),
+            funded_slots: vec![],
+        })
+    }
+
+    /// governance and funded slots together
+    pub fn all_slots(&self) -> Vec<DistributionSchedule> {
+        self.distribution_schedule
+            .iter()
+            .chain(self.funded_slots.iter().map(|funded| &funded.slot))
+            .cloned()
+            .collect()
+    }
+
+    pub fn to_normal(&self, api: &dyn Api) -> StdResult<RewardAsset> {
+        Ok(RewardAsset {
+            info: self.info.to_normal(api)?,
+            distribution_schedule: self.distribution_schedule.clone(),
+        })
+    }
 }
 
 pub fn store_config(storage: &mut dyn Storage, config: &Config) -> StdResult<()> {
@@ -28,8 +92,19 @@ pub fn read_config(storage: &dyn Storage) -> StdResult<Config> {
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct State {
     pub last_distributed: u64,
+    /// Bond shares of all stakers
     pub total_bond_amount: Uint128,
-    pub global_reward_index: Decimal,
+    pub total_weight: Uint128,
+    /// Unbonded tokens still waiting in unbonding queues
+    pub total_unbonding_amount: Uint128,
+    pub global_reward_indexes: Vec<Decimal256>,
+    /// Distributed amounts the indexes could not represent yet,
+    /// carried into the next distribution per reward asset
+    pub reward_dust: Vec<Decimal256>,
+    /// Rewards distributed to stakers but not paid out yet, per reward asset
+    pub unclaimed_rewards: Vec<Uint128>,
+    /// Staking tokens a bond share is worth; pool-wide slashes lower it
+    pub share_price: Decimal256,
 }
 
 pub fn store_state(storage: &mut dyn Storage, state: &State) -> StdResult<()> {
@@ -40,11 +115,57 @@ pub fn read_state(storage: &dyn Storage) -> StdResult<State> {
     singleton_read(storage, KEY_STATE).load()
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
+pub struct PauseInfo {
+    pub bond: bool,
+    pub unbond: bool,
+    pub withdraw: bool,
+    pub migrate_staking: bool,
+    /// Set by governance only; lets stakers use EmergencyUnbond
+    /// while unbond is paused
+    pub emergency_unbond: bool,
+}
+
+impl PauseInfo {
+    pub fn is_paused(&self, operation: &Operation) -> bool {
+        match operation {
+            Operation::Bond => self.bond,
+            Operation::Unbond => self.unbond,
+            Operation::Withdraw => self.withdraw,
+            Operation::MigrateStaking => self.migrate_staking,
+        }
+    }
+
+    pub fn set_paused(&mut self, operation: &Operation, paused: bool) {
+        match operation {
+            Operation::Bond => self.bond = paused,
+            Operation::Unbond => self.unbond = paused,
+            Operation::Withdraw => self.withdraw = paused,
+            Operation::MigrateStaking => self.migrate_staking = paused,
+        }
+    }
+}
+
+pub fn store_pause_info(storage: &mut dyn Storage, pause_info: &PauseInfo) -> StdResult<()> {
+    singleton(storage, KEY_PAUSE).save(pause_info)
+}
+
+/// nothing is paused until the guardian says otherwise
+pub fn read_pause_info(storage: &dyn Storage) -> StdResult<PauseInfo> {
+    Ok(singleton_read(storage, KEY_PAUSE)
+        .may_load()?
+        .unwrap_or_default())
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct StakerInfo {
-    pub reward_index: Decimal,
+    pub reward_indexes: Vec<Decimal256>,
+    /// Bond shares, each worth `State::share_price` staking tokens
     pub bond_amount: Uint128,
-    pub pending_reward: Uint128,
+    pub pending_rewards: Vec<Uint128>,
+    pub boost: Decimal,
+    pub lock_end: u64,
+    pub auto_compound: bool,
 }
 
 /// returns return staker_info of the given owner
@@ -67,9 +188,146 @@ pub fn read_staker_info(storage: &dyn Storage, owner: &CanonicalAddr) -> StdResu
     match ReadonlyBucket::new(storage, PREFIX_REWARD).may_load(owner.as_slice())? {
         Some(staker_info) => Ok(staker_info),
         None => Ok(StakerInfo {
-            reward_index: Decimal::zero(),
+            reward_indexes: vec![],
             bond_amount: Uint128::zero(),
-            pending_reward: Uint128::zero(),
+            pending_rewards: vec![],
+            boost: Decimal::one(),
+            lock_end: 0,
+            auto_compound: false,
         }),
     }
 }
+
+/// returns staker_infos ordered by canonical address, starting after the given owner
+pub fn read_staker_infos(
+    storage: &dyn Storage,
+    start_after: Option<CanonicalAddr>,
+    limit: Option<u32>,
+) -> StdResult<Vec<(CanonicalAddr, StakerInfo)>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = calc_range_start(start_after);
+
+    ReadonlyBucket::new(storage, PREFIX_REWARD)
+        .range(start.as_deref(), None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let (k, v) = item?;
+            Ok((CanonicalAddr::from(k), v))
+        })
+        .collect()
+}
+
+// this will set the first key after the provided key, by appending a 1 byte
+fn calc_range_start(start_after: Option<CanonicalAddr>) -> Option<Vec<u8>> {
+    start_after.map(|addr| {
+        let mut v = addr.as_slice().to_vec();
+        v.push(1);
+        v
+    })
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct UnbondingInfo {
+    pub amount: Uint128,
+    pub release_time: u64,
+}
+
+/// stores the unbonding queue of the given owner
+pub fn store_unbondings(
+    storage: &mut dyn Storage,
+    owner: &CanonicalAddr,
+    unbondings: &[UnbondingInfo],
+) -> StdResult<()> {
+    Bucket::new(storage, PREFIX_UNBONDING).save(owner.as_slice(), &unbondings.to_vec())
+}
+
+/// remove the unbonding queue of the given owner
+pub fn remove_unbondings(storage: &mut dyn Storage, owner: &CanonicalAddr) {
+    Bucket::<Vec<UnbondingInfo>>::new(storage, PREFIX_UNBONDING).remove(owner.as_slice())
+}
+
+/// returns the unbonding queue of the given owner, oldest first
+pub fn read_unbondings(
+    storage: &dyn Storage,
+    owner: &CanonicalAddr,
+) -> StdResult<Vec<UnbondingInfo>> {
+    Ok(ReadonlyBucket::new(storage, PREFIX_UNBONDING)
+        .may_load(owner.as_slice())?
+        .unwrap_or_default())
+}
+
+/// Addresses a staker lets withdraw rewards on its behalf and
+/// the recipients those operators may pay out to
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
+pub struct WithdrawPermissions {
+    pub operators: Vec<CanonicalAddr>,
+    pub recipients: Vec<CanonicalAddr>,
+}
+
+pub fn store_withdraw_permissions(
+    storage: &mut dyn Storage,
+    staker: &CanonicalAddr,
+    permissions: &WithdrawPermissions,
+) -> StdResult<()> {
+    if permissions.operators.is_empty() && permissions.recipients.is_empty() {
+        Bucket::<WithdrawPermissions>::new(storage, PREFIX_WITHDRAW_PERMISSIONS)
+            .remove(staker.as_slice());
+        return Ok(());
+    }
+
+    Bucket::new(storage, PREFIX_WITHDRAW_PERMISSIONS).save(staker.as_slice(), permissions)
+}
+
+/// returns the withdraw permissions of the given staker,
+/// empty when none were granted
+pub fn read_withdraw_permissions(
+    storage: &dyn Storage,
+    staker: &CanonicalAddr,
+) -> StdResult<WithdrawPermissions> {
+    Ok(ReadonlyBucket::new(storage, PREFIX_WITHDRAW_PERMISSIONS)
+        .may_load(staker.as_slice())?
+        .unwrap_or_default())
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct QueuedScheduleChange {
+    pub change: ScheduleChange,
+    pub eta: u64,
+}
+
+/// queues the change under a new id and returns the id
+pub fn store_schedule_change(
+    storage: &mut dyn Storage,
+    queued_change: &QueuedScheduleChange,
+) -> StdResult<u64> {
+    let id: u64 = singleton_read(storage, KEY_SCHEDULE_CHANGE_COUNT)
+        .may_load()?
+        .unwrap_or_default();
+    singleton(storage, KEY_SCHEDULE_CHANGE_COUNT).save(&(id + 1))?;
+
+    Bucket::new(storage, PREFIX_SCHEDULE_CHANGE).save(&id.to_be_bytes(), queued_change)?;
+    Ok(id)
+}
+
+pub fn remove_schedule_change(storage: &mut dyn Storage, id: u64) {
+    Bucket::<QueuedScheduleChange>::new(storage, PREFIX_SCHEDULE_CHANGE).remove(&id.to_be_bytes())
+}
+
+pub fn read_schedule_change(storage: &dyn Storage, id: u64) -> StdResult<QueuedScheduleChange> {
+    ReadonlyBucket::new(storage, PREFIX_SCHEDULE_CHANGE)
+        .may_load(&id.to_be_bytes())?
+        .ok_or_else(|| StdError::generic_err("schedule change not found"))
+}
+
+/// returns the queued schedule changes, oldest first
+pub fn read_schedule_changes(storage: &dyn Storage) -> StdResult<Vec<(u64, QueuedScheduleChange)>> {
+    ReadonlyBucket::new(storage, PREFIX_SCHEDULE_CHANGE)
+        .range(None, None, Order::Ascending)
+        .map(|item| {
+            let (k, v) = item?;
+            let mut id = [0u8; 8];
+            id.copy_from_slice(&k);
+            Ok((u64::from_be_bytes(id), v))
+        })
+        .collect()
+}
@@ -1,13 +1,24 @@
-use crate::contract::{execute, instantiate, query};
-use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+use crate::contract::{execute, instantiate, migrate, query};
+use crate::migration::{LegacyConfig, LegacyStakerInfo, LegacyState};
+use crate::mock_querier::mock_dependencies as mock_dependencies_with_tax;
+use cosmwasm_bignumber::Decimal256;
+use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info, MOCK_CONTRACT_ADDR};
 use cosmwasm_std::{
-    attr, from_binary, to_binary, CosmosMsg, Decimal, StdError, SubMsg, Uint128, WasmMsg,
+    attr, from_binary, to_binary, Api, BankMsg, Coin, CosmosMsg, Decimal, StdError, SubMsg,
+    Uint128, WasmMsg,
 };
+use cosmwasm_storage::{singleton, Bucket};
+use cw2::{get_contract_version, set_contract_version, CONTRACT};
 use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+use gohm_staking::asset::AssetInfo;
 use gohm_staking::staking::ExecuteMsg::UpdateConfig;
 use gohm_staking::staking::{
-    ConfigResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, QueryMsg, StakerInfoResponse,
-    StateResponse,
+    AllStakersResponse, ConfigResponse, Cw20HookMsg, DistributionSchedule, ExecuteMsg,
+    FundedSlotResponse, InstantiateMsg, MigrateMsg, Operation, PauseStatusResponse,
+    PendingGovernanceResponse, QueryMsg, RewardAsset, RewardAssetRateResponse, RewardRateResponse,
+    RewardSolvencyResponse, ScheduleChange, ScheduleChangeResponse, SolvencyResponse,
+    StakerInfoResponse, StateResponse, UnbondingResponse, UnbondingsResponse,
+    WithdrawPermissionsResponse,
 };
 
 #[test]
@@ -15,10 +26,23 @@ fn proper_initialization() {
     let mut deps = mock_dependencies(&[]);
 
     let msg = InstantiateMsg {
-        reward_token: "reward0000".to_string(),
         staking_token: "staking0000".to_string(),
-        distribution_schedule: vec![(100, 200, Uint128::from(1000000u128))],
+        rewards: vec![RewardAsset {
+            info: AssetInfo::Token {
+                contract_addr: "reward0000".to_string(),
+            },
+            distribution_schedule: vec![DistributionSchedule {
+                start_time: 100,
+                end_time: 200,
+                amount: Uint128::from(1000000u128),
+            }],
+        }],
         governance: "gov0000".to_string(),
+        guardian: None,
+        unbonding_period: 0,
+        boost_curve: vec![],
+        reject_underfunded_schedules: false,
+        schedule_timelock: 0,
     };
 
     let info = mock_info("addr0000", &[]);
@@ -32,10 +56,26 @@ fn proper_initialization() {
     assert_eq!(
         config,
         ConfigResponse {
-            reward_token: "reward0000".to_string(),
             staking_token: "staking0000".to_string(),
-            distribution_schedule: vec![(100, 200, Uint128::from(1000000u128))],
+            rewards: vec![RewardAsset {
+                info: AssetInfo::Token {
+                    contract_addr: "reward0000".to_string()
+                },
+                distribution_schedule: vec![DistributionSchedule {
+                    start_time: 100,
+                    end_time: 200,
+                    amount: Uint128::from(1000000u128)
+                }],
+            }],
             governance: "gov0000".to_string(),
+            guardian: None,
+            unbonding_period: 0,
+            boost_curve: vec![],
+            reject_underfunded_schedules: false,
+            pending_governance: None,
+            schedule_timelock: 0,
+            migrated_to: None,
+            funded_slots: vec![],
         }
     );
 
@@ -51,7 +91,9 @@ fn proper_initialization() {
         StateResponse {
             last_distributed: mock_env().block.time.seconds(),
             total_bond_amount: Uint128::zero(),
-            global_reward_index: Decimal::zero(),
+            total_weight: Uint128::zero(),
+            global_reward_indexes: vec![("reward0000".to_string(), Decimal256::zero())],
+            share_price: Decimal256::one(),
         }
     );
 }
@@ -61,21 +103,30 @@ fn test_bond_tokens() {
     let mut deps = mock_dependencies(&[]);
 
     let msg = InstantiateMsg {
-        reward_token: "reward0000".to_string(),
         staking_token: "staking0000".to_string(),
-        distribution_schedule: vec![
-            (
-                mock_env().block.time.seconds(),
-                mock_env().block.time.seconds() + 100,
-                Uint128::from(1000000u128),
-            ),
-            (
-                mock_env().block.time.seconds() + 100,
-                mock_env().block.time.seconds() + 200,
-                Uint128::from(10000000u128),
-            ),
-        ],
+        rewards: vec![RewardAsset {
+            info: AssetInfo::Token {
+                contract_addr: "reward0000".to_string(),
+            },
+            distribution_schedule: vec![
+                DistributionSchedule {
+                    start_time: mock_env().block.time.seconds(),
+                    end_time: mock_env().block.time.seconds() + 100,
+                    amount: Uint128::from(1000000u128),
+                },
+                DistributionSchedule {
+                    start_time: mock_env().block.time.seconds() + 100,
+                    end_time: mock_env().block.time.seconds() + 200,
+                    amount: Uint128::from(10000000u128),
+                },
+            ],
+        }],
         governance: "gov0000".to_string(),
+        guardian: None,
+        unbonding_period: 0,
+        boost_curve: vec![],
+        reject_underfunded_schedules: false,
+        schedule_timelock: 0,
     };
 
     let info = mock_info("addr0000", &[]);
@@ -84,7 +135,11 @@ fn test_bond_tokens() {
     let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
         sender: "addr0000".to_string(),
         amount: Uint128::from(100u128),
-        msg: to_binary(&Cw20HookMsg::Bond {}).unwrap(),
+        msg: to_binary(&Cw20HookMsg::Bond {
+            lock_duration: None,
+            beneficiary: None,
+        })
+        .unwrap(),
     });
 
     let info = mock_info("staking0000", &[]);
@@ -106,9 +161,12 @@ fn test_bond_tokens() {
         .unwrap(),
         StakerInfoResponse {
             staker: "addr0000".to_string(),
-            reward_index: Decimal::zero(),
-            pending_reward: Uint128::zero(),
+            reward_indexes: vec![("reward0000".to_string(), Decimal256::zero())],
+            pending_rewards: vec![("reward0000".to_string(), Uint128::zero())],
             bond_amount: Uint128::from(100u128),
+            boost: Decimal::one(),
+            lock_end: 0,
+            auto_compound: false,
         }
     );
 
@@ -124,8 +182,10 @@ fn test_bond_tokens() {
         .unwrap(),
         StateResponse {
             total_bond_amount: Uint128::from(100u128),
-            global_reward_index: Decimal::zero(),
+            total_weight: Uint128::from(100u128),
+            global_reward_indexes: vec![("reward0000".to_string(), Decimal256::zero())],
             last_distributed: mock_env().block.time.seconds(),
+            share_price: Decimal256::one(),
         }
     );
 
@@ -133,7 +193,11 @@ fn test_bond_tokens() {
     let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
         sender: "addr0000".to_string(),
         amount: Uint128::from(100u128),
-        msg: to_binary(&Cw20HookMsg::Bond {}).unwrap(),
+        msg: to_binary(&Cw20HookMsg::Bond {
+            lock_duration: None,
+            beneficiary: None,
+        })
+        .unwrap(),
     });
     env.block.time = env.block.time.plus_seconds(10);
 
@@ -154,9 +218,15 @@ fn test_bond_tokens() {
         .unwrap(),
         StakerInfoResponse {
             staker: "addr0000".to_string(),
-            reward_index: Decimal::from_ratio(1000u128, 1u128),
-            pending_reward: Uint128::from(100000u128),
+            reward_indexes: vec![(
+                "reward0000".to_string(),
+                Decimal256::from_ratio(1000u64, 1u64)
+            )],
+            pending_rewards: vec![("reward0000".to_string(), Uint128::from(100000u128))],
             bond_amount: Uint128::from(200u128),
+            boost: Decimal::one(),
+            lock_end: 0,
+            auto_compound: false,
         }
     );
 
@@ -172,8 +242,13 @@ fn test_bond_tokens() {
         .unwrap(),
         StateResponse {
             total_bond_amount: Uint128::from(200u128),
-            global_reward_index: Decimal::from_ratio(1000u128, 1u128),
+            total_weight: Uint128::from(200u128),
+            global_reward_indexes: vec![(
+                "reward0000".to_string(),
+                Decimal256::from_ratio(1000u64, 1u64)
+            )],
             last_distributed: mock_env().block.time.seconds() + 10,
+            share_price: Decimal256::one(),
         }
     );
 
@@ -181,7 +256,11 @@ fn test_bond_tokens() {
     let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
         sender: "addr0000".to_string(),
         amount: Uint128::from(100u128),
-        msg: to_binary(&Cw20HookMsg::Bond {}).unwrap(),
+        msg: to_binary(&Cw20HookMsg::Bond {
+            lock_duration: None,
+            beneficiary: None,
+        })
+        .unwrap(),
     });
 
     let info = mock_info("staking0001", &[]);
@@ -197,13 +276,30 @@ fn test_unbond() {
     let mut deps = mock_dependencies(&[]);
 
     let msg = InstantiateMsg {
-        reward_token: "reward0000".to_string(),
         staking_token: "staking0000".to_string(),
-        distribution_schedule: vec![
-            (12345, 12345 + 100, Uint128::from(1000000u128)),
-            (12345 + 100, 12345 + 200, Uint128::from(10000000u128)),
-        ],
+        rewards: vec![RewardAsset {
+            info: AssetInfo::Token {
+                contract_addr: "reward0000".to_string(),
+            },
+            distribution_schedule: vec![
+                DistributionSchedule {
+                    start_time: 12345,
+                    end_time: 12345 + 100,
+                    amount: Uint128::from(1000000u128),
+                },
+                DistributionSchedule {
+                    start_time: 12345 + 100,
+                    end_time: 12345 + 200,
+                    amount: Uint128::from(10000000u128),
+                },
+            ],
+        }],
         governance: "gov0000".to_string(),
+        guardian: None,
+        unbonding_period: 0,
+        boost_curve: vec![],
+        reject_underfunded_schedules: false,
+        schedule_timelock: 0,
     };
 
     let info = mock_info("addr0000", &[]);
@@ -213,7 +309,11 @@ fn test_unbond() {
     let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
         sender: "addr0000".to_string(),
         amount: Uint128::from(100u128),
-        msg: to_binary(&Cw20HookMsg::Bond {}).unwrap(),
+        msg: to_binary(&Cw20HookMsg::Bond {
+            lock_duration: None,
+            beneficiary: None,
+        })
+        .unwrap(),
     });
     let info = mock_info("staking0000", &[]);
     let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
@@ -254,25 +354,207 @@ fn test_unbond() {
 }
 
 #[test]
-fn test_compute_reward() {
+fn test_unbond_with_unbonding_period() {
     let mut deps = mock_dependencies(&[]);
 
     let msg = InstantiateMsg {
-        reward_token: "reward0000".to_string(),
         staking_token: "staking0000".to_string(),
-        distribution_schedule: vec![
-            (
-                mock_env().block.time.seconds(),
-                mock_env().block.time.seconds() + 100,
-                Uint128::from(1000000u128),
-            ),
-            (
-                mock_env().block.time.seconds() + 100,
-                mock_env().block.time.seconds() + 200,
-                Uint128::from(10000000u128),
+        rewards: vec![RewardAsset {
+            info: AssetInfo::Token {
+                contract_addr: "reward0000".to_string(),
+            },
+            distribution_schedule: vec![
+                DistributionSchedule {
+                    start_time: 12345,
+                    end_time: 12345 + 100,
+                    amount: Uint128::from(1000000u128),
+                },
+                DistributionSchedule {
+                    start_time: 12345 + 100,
+                    end_time: 12345 + 200,
+                    amount: Uint128::from(10000000u128),
+                },
+            ],
+        }],
+        governance: "gov0000".to_string(),
+        guardian: None,
+        unbonding_period: 100,
+        boost_curve: vec![],
+        reject_underfunded_schedules: false,
+        schedule_timelock: 0,
+    };
+
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    // bond 100 tokens
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "addr0000".to_string(),
+        amount: Uint128::from(100u128),
+        msg: to_binary(&Cw20HookMsg::Bond {
+            lock_duration: None,
+            beneficiary: None,
+        })
+        .unwrap(),
+    });
+    let info = mock_info("staking0000", &[]);
+    let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    // unbond 60 tokens; queued instead of transferred
+    let msg = ExecuteMsg::Unbond {
+        amount: Uint128::from(60u128),
+    };
+    let info = mock_info("addr0000", &[]);
+    let mut env = mock_env();
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+    assert_eq!(res.messages, vec![]);
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "unbond"),
+            attr("owner", "addr0000"),
+            attr("amount", "60"),
+            attr(
+                "release_time",
+                (mock_env().block.time.seconds() + 100).to_string()
             ),
-        ],
+        ]
+    );
+
+    // unbond 40 more tokens after 50 seconds
+    env.block.time = env.block.time.plus_seconds(50);
+    let msg = ExecuteMsg::Unbond {
+        amount: Uint128::from(40u128),
+    };
+    let _res = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+    // nothing has matured yet
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        ExecuteMsg::Claim {},
+    );
+    match res {
+        Err(StdError::GenericErr { msg, .. }) => assert_eq!(msg, "Nothing to claim"),
+        _ => panic!("Must return generic error"),
+    }
+
+    // first unbonding matures
+    env.block.time = env.block.time.plus_seconds(50);
+    assert_eq!(
+        from_binary::<UnbondingsResponse>(
+            &query(
+                deps.as_ref(),
+                env.clone(),
+                QueryMsg::Unbondings {
+                    staker: "addr0000".to_string(),
+                },
+            )
+            .unwrap()
+        )
+        .unwrap(),
+        UnbondingsResponse {
+            staker: "addr0000".to_string(),
+            unbondings: vec![
+                UnbondingResponse {
+                    amount: Uint128::from(60u128),
+                    release_time: mock_env().block.time.seconds() + 100,
+                    matured: true,
+                },
+                UnbondingResponse {
+                    amount: Uint128::from(40u128),
+                    release_time: mock_env().block.time.seconds() + 150,
+                    matured: false,
+                },
+            ],
+        }
+    );
+
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        ExecuteMsg::Claim {},
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "staking0000".to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: "addr0000".to_string(),
+                amount: Uint128::from(60u128),
+            })
+            .unwrap(),
+            funds: vec![],
+        }))]
+    );
+
+    // second unbonding matures
+    env.block.time = env.block.time.plus_seconds(50);
+    let res = execute(deps.as_mut(), env.clone(), info, ExecuteMsg::Claim {}).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "staking0000".to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: "addr0000".to_string(),
+                amount: Uint128::from(40u128),
+            })
+            .unwrap(),
+            funds: vec![],
+        }))]
+    );
+
+    assert_eq!(
+        from_binary::<UnbondingsResponse>(
+            &query(
+                deps.as_ref(),
+                env,
+                QueryMsg::Unbondings {
+                    staker: "addr0000".to_string(),
+                },
+            )
+            .unwrap()
+        )
+        .unwrap(),
+        UnbondingsResponse {
+            staker: "addr0000".to_string(),
+            unbondings: vec![],
+        }
+    );
+}
+
+#[test]
+fn test_compute_reward() {
+    let mut deps = mock_dependencies(&[]);
+
+    let msg = InstantiateMsg {
+        staking_token: "staking0000".to_string(),
+        rewards: vec![RewardAsset {
+            info: AssetInfo::Token {
+                contract_addr: "reward0000".to_string(),
+            },
+            distribution_schedule: vec![
+                DistributionSchedule {
+                    start_time: mock_env().block.time.seconds(),
+                    end_time: mock_env().block.time.seconds() + 100,
+                    amount: Uint128::from(1000000u128),
+                },
+                DistributionSchedule {
+                    start_time: mock_env().block.time.seconds() + 100,
+                    end_time: mock_env().block.time.seconds() + 200,
+                    amount: Uint128::from(10000000u128),
+                },
+            ],
+        }],
         governance: "gov0000".to_string(),
+        guardian: None,
+        unbonding_period: 0,
+        boost_curve: vec![],
+        reject_underfunded_schedules: false,
+        schedule_timelock: 0,
     };
 
     let info = mock_info("addr0000", &[]);
@@ -282,7 +564,11 @@ fn test_compute_reward() {
     let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
         sender: "addr0000".to_string(),
         amount: Uint128::from(100u128),
-        msg: to_binary(&Cw20HookMsg::Bond {}).unwrap(),
+        msg: to_binary(&Cw20HookMsg::Bond {
+            lock_duration: None,
+            beneficiary: None,
+        })
+        .unwrap(),
     });
     let info = mock_info("staking0000", &[]);
     let mut env = mock_env();
@@ -296,7 +582,11 @@ fn test_compute_reward() {
     let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
         sender: "addr0000".to_string(),
         amount: Uint128::from(100u128),
-        msg: to_binary(&Cw20HookMsg::Bond {}).unwrap(),
+        msg: to_binary(&Cw20HookMsg::Bond {
+            lock_duration: None,
+            beneficiary: None,
+        })
+        .unwrap(),
     });
     let _res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
 
@@ -315,9 +605,15 @@ fn test_compute_reward() {
         .unwrap(),
         StakerInfoResponse {
             staker: "addr0000".to_string(),
-            reward_index: Decimal::from_ratio(10000u128, 1u128),
-            pending_reward: Uint128::from(1000000u128),
+            reward_indexes: vec![(
+                "reward0000".to_string(),
+                Decimal256::from_ratio(10000u64, 1u64)
+            )],
+            pending_rewards: vec![("reward0000".to_string(), Uint128::from(1000000u128))],
             bond_amount: Uint128::from(200u128),
+            boost: Decimal::one(),
+            lock_end: 0,
+            auto_compound: false,
         }
     );
 
@@ -346,9 +642,15 @@ fn test_compute_reward() {
         .unwrap(),
         StakerInfoResponse {
             staker: "addr0000".to_string(),
-            reward_index: Decimal::from_ratio(15000u64, 1u64),
-            pending_reward: Uint128::from(2000000u128),
+            reward_indexes: vec![(
+                "reward0000".to_string(),
+                Decimal256::from_ratio(15000u64, 1u64)
+            )],
+            pending_rewards: vec![("reward0000".to_string(), Uint128::from(2000000u128))],
             bond_amount: Uint128::from(100u128),
+            boost: Decimal::one(),
+            lock_end: 0,
+            auto_compound: false,
         }
     );
 
@@ -368,9 +670,112 @@ fn test_compute_reward() {
         .unwrap(),
         StakerInfoResponse {
             staker: "addr0000".to_string(),
-            reward_index: Decimal::from_ratio(25000u64, 1u64),
-            pending_reward: Uint128::from(3000000u128),
+            reward_indexes: vec![(
+                "reward0000".to_string(),
+                Decimal256::from_ratio(25000u64, 1u64)
+            )],
+            pending_rewards: vec![("reward0000".to_string(), Uint128::from(3000000u128))],
             bond_amount: Uint128::from(100u128),
+            boost: Decimal::one(),
+            lock_end: 0,
+            auto_compound: false,
+        }
+    );
+}
+
+#[test]
+fn test_query_all_stakers() {
+    let mut deps = mock_dependencies(&[]);
+
+    let msg = InstantiateMsg {
+        staking_token: "staking0000".to_string(),
+        rewards: vec![RewardAsset {
+            info: AssetInfo::Token {
+                contract_addr: "reward0000".to_string(),
+            },
+            distribution_schedule: vec![DistributionSchedule {
+                start_time: mock_env().block.time.seconds(),
+                end_time: mock_env().block.time.seconds() + 100,
+                amount: Uint128::from(1000000u128),
+            }],
+        }],
+        governance: "gov0000".to_string(),
+        guardian: None,
+        unbonding_period: 0,
+        boost_curve: vec![],
+        reject_underfunded_schedules: false,
+        schedule_timelock: 0,
+    };
+
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    // bond 100 tokens from three stakers
+    let info = mock_info("staking0000", &[]);
+    for staker in ["addr0000", "addr0001", "addr0002"].iter() {
+        let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: staker.to_string(),
+            amount: Uint128::from(100u128),
+            msg: to_binary(&Cw20HookMsg::Bond {
+                lock_duration: None,
+                beneficiary: None,
+            })
+            .unwrap(),
+        });
+        let _res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+    }
+
+    let res: AllStakersResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::AllStakers {
+                start_after: None,
+                limit: Some(2),
+                block_time: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        res.stakers
+            .iter()
+            .map(|staker| staker.staker.as_str())
+            .collect::<Vec<&str>>(),
+        vec!["addr0000", "addr0001"]
+    );
+
+    // next page with pending rewards projected 30 seconds ahead;
+    // 300,000 rewards distributed over 300 bonded tokens
+    let res: AllStakersResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::AllStakers {
+                start_after: Some("addr0001".to_string()),
+                limit: None,
+                block_time: Some(mock_env().block.time.plus_seconds(30).seconds()),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        res,
+        AllStakersResponse {
+            stakers: vec![StakerInfoResponse {
+                staker: "addr0002".to_string(),
+                reward_indexes: vec![(
+                    "reward0000".to_string(),
+                    Decimal256::from_ratio(1000u64, 1u64)
+                )],
+                pending_rewards: vec![("reward0000".to_string(), Uint128::from(100000u128))],
+                bond_amount: Uint128::from(100u128),
+                boost: Decimal::one(),
+                lock_end: 0,
+                auto_compound: false,
+            }],
         }
     );
 }
@@ -380,21 +785,30 @@ fn test_withdraw() {
     let mut deps = mock_dependencies(&[]);
 
     let msg = InstantiateMsg {
-        reward_token: "reward0000".to_string(),
         staking_token: "staking0000".to_string(),
-        distribution_schedule: vec![
-            (
-                mock_env().block.time.seconds(),
-                mock_env().block.time.seconds() + 100,
-                Uint128::from(1000000u128),
-            ),
-            (
-                mock_env().block.time.seconds() + 100,
-                mock_env().block.time.seconds() + 200,
-                Uint128::from(10000000u128),
-            ),
-        ],
+        rewards: vec![RewardAsset {
+            info: AssetInfo::Token {
+                contract_addr: "reward0000".to_string(),
+            },
+            distribution_schedule: vec![
+                DistributionSchedule {
+                    start_time: mock_env().block.time.seconds(),
+                    end_time: mock_env().block.time.seconds() + 100,
+                    amount: Uint128::from(1000000u128),
+                },
+                DistributionSchedule {
+                    start_time: mock_env().block.time.seconds() + 100,
+                    end_time: mock_env().block.time.seconds() + 200,
+                    amount: Uint128::from(10000000u128),
+                },
+            ],
+        }],
         governance: "gov0000".to_string(),
+        guardian: None,
+        unbonding_period: 0,
+        boost_curve: vec![],
+        reject_underfunded_schedules: false,
+        schedule_timelock: 0,
     };
 
     let info = mock_info("addr0000", &[]);
@@ -404,7 +818,11 @@ fn test_withdraw() {
     let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
         sender: "addr0000".to_string(),
         amount: Uint128::from(100u128),
-        msg: to_binary(&Cw20HookMsg::Bond {}).unwrap(),
+        msg: to_binary(&Cw20HookMsg::Bond {
+            lock_duration: None,
+            beneficiary: None,
+        })
+        .unwrap(),
     });
     let info = mock_info("staking0000", &[]);
     let mut env = mock_env();
@@ -416,7 +834,10 @@ fn test_withdraw() {
 
     let info = mock_info("addr0000", &[]);
 
-    let msg = ExecuteMsg::Withdraw {};
+    let msg = ExecuteMsg::Withdraw {
+        staker: None,
+        recipient: None,
+    };
     let res = execute(deps.as_mut(), env, info, msg).unwrap();
 
     assert_eq!(
@@ -434,25 +855,39 @@ fn test_withdraw() {
 }
 
 #[test]
-fn test_migrate_staking() {
+fn test_withdraw_multiple_reward_assets() {
     let mut deps = mock_dependencies(&[]);
 
     let msg = InstantiateMsg {
-        reward_token: "reward0000".to_string(),
         staking_token: "staking0000".to_string(),
-        distribution_schedule: vec![
-            (
-                mock_env().block.time.seconds(),
-                mock_env().block.time.seconds() + 100,
-                Uint128::from(1000000u128),
-            ),
-            (
-                mock_env().block.time.seconds() + 100,
-                mock_env().block.time.seconds() + 200,
-                Uint128::from(10000000u128),
-            ),
+        rewards: vec![
+            RewardAsset {
+                info: AssetInfo::Token {
+                    contract_addr: "reward0000".to_string(),
+                },
+                distribution_schedule: vec![DistributionSchedule {
+                    start_time: mock_env().block.time.seconds(),
+                    end_time: mock_env().block.time.seconds() + 100,
+                    amount: Uint128::from(1000000u128),
+                }],
+            },
+            RewardAsset {
+                info: AssetInfo::Token {
+                    contract_addr: "reward0001".to_string(),
+                },
+                distribution_schedule: vec![DistributionSchedule {
+                    start_time: mock_env().block.time.seconds() + 50,
+                    end_time: mock_env().block.time.seconds() + 150,
+                    amount: Uint128::from(2000000u128),
+                }],
+            },
         ],
         governance: "gov0000".to_string(),
+        guardian: None,
+        unbonding_period: 0,
+        boost_curve: vec![],
+        reject_underfunded_schedules: false,
+        schedule_timelock: 0,
     };
 
     let info = mock_info("addr0000", &[]);
@@ -462,95 +897,525 @@ fn test_migrate_staking() {
     let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
         sender: "addr0000".to_string(),
         amount: Uint128::from(100u128),
-        msg: to_binary(&Cw20HookMsg::Bond {}).unwrap(),
+        msg: to_binary(&Cw20HookMsg::Bond {
+            lock_duration: None,
+            beneficiary: None,
+        })
+        .unwrap(),
     });
     let info = mock_info("staking0000", &[]);
     let mut env = mock_env();
     let _res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
 
-    // 100 seconds is passed
-    // 1,000,000 rewards distributed
-    env.block.time = env.block.time.plus_seconds(100);
-    let info = mock_info("addr0000", &[]);
+    // append a third reward asset; its index starts from zero
+    let update_config = UpdateConfig {
+        guardian: None,
+        boost_curve: None,
+        reject_underfunded_schedules: None,
+        schedule_timelock: None,
+        rewards: Some(vec![
+            RewardAsset {
+                info: AssetInfo::Token {
+                    contract_addr: "reward0000".to_string(),
+                },
+                distribution_schedule: vec![DistributionSchedule {
+                    start_time: mock_env().block.time.seconds(),
+                    end_time: mock_env().block.time.seconds() + 100,
+                    amount: Uint128::from(1000000u128),
+                }],
+            },
+            RewardAsset {
+                info: AssetInfo::Token {
+                    contract_addr: "reward0001".to_string(),
+                },
+                distribution_schedule: vec![DistributionSchedule {
+                    start_time: mock_env().block.time.seconds() + 50,
+                    end_time: mock_env().block.time.seconds() + 150,
+                    amount: Uint128::from(2000000u128),
+                }],
+            },
+            RewardAsset {
+                info: AssetInfo::Token {
+                    contract_addr: "reward0002".to_string(),
+                },
+                distribution_schedule: vec![DistributionSchedule {
+                    start_time: mock_env().block.time.seconds() + 200,
+                    end_time: mock_env().block.time.seconds() + 300,
+                    amount: Uint128::from(3000000u128),
+                }],
+            },
+        ]),
+    };
+    let info = mock_info("gov0000", &[]);
+    let _res = execute(deps.as_mut(), env.clone(), info, update_config).unwrap();
 
-    let msg = ExecuteMsg::Withdraw {};
-    let res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+    // 100 seconds passed
+    // 1,000,000 reward0000 and 1,000,000 reward0001 distributed
+    env.block.time = env.block.time.plus_seconds(100);
+    assert_eq!(
+        from_binary::<StakerInfoResponse>(
+            &query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::StakerInfo {
+                    staker: "addr0000".to_string(),
+                    block_time: Some(env.block.time.seconds()),
+                },
+            )
+            .unwrap()
+        )
+        .unwrap(),
+        StakerInfoResponse {
+            staker: "addr0000".to_string(),
+            reward_indexes: vec![
+                (
+                    "reward0000".to_string(),
+                    Decimal256::from_ratio(10000u64, 1u64)
+                ),
+                (
+                    "reward0001".to_string(),
+                    Decimal256::from_ratio(10000u64, 1u64)
+                ),
+                ("reward0002".to_string(), Decimal256::zero()),
+            ],
+            pending_rewards: vec![
+                ("reward0000".to_string(), Uint128::from(1000000u128)),
+                ("reward0001".to_string(), Uint128::from(1000000u128)),
+                ("reward0002".to_string(), Uint128::zero()),
+            ],
+            bond_amount: Uint128::from(100u128),
+            boost: Decimal::one(),
+            lock_end: 0,
+            auto_compound: false,
+        }
+    );
 
+    let info = mock_info("addr0000", &[]);
+    let res = execute(
+        deps.as_mut(),
+        env,
+        info,
+        ExecuteMsg::Withdraw {
+            staker: None,
+            recipient: None,
+        },
+    )
+    .unwrap();
     assert_eq!(
         res.messages,
-        vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
-            contract_addr: "reward0000".to_string(),
-            msg: to_binary(&Cw20ExecuteMsg::Transfer {
-                recipient: "addr0000".to_string(),
-                amount: Uint128::from(1000000u128),
-            })
-            .unwrap(),
-            funds: vec![],
-        }))]
+        vec![
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "reward0000".to_string(),
+                msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: "addr0000".to_string(),
+                    amount: Uint128::from(1000000u128),
+                })
+                .unwrap(),
+                funds: vec![],
+            })),
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "reward0001".to_string(),
+                msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: "addr0000".to_string(),
+                    amount: Uint128::from(1000000u128),
+                })
+                .unwrap(),
+                funds: vec![],
+            })),
+        ]
     );
 
-    // execute migration after 50 seconds
-    env.block.time = env.block.time.plus_seconds(50);
-
-    let msg = ExecuteMsg::MigrateStaking {
-        new_staking_contract: "newstaking0000".to_string(),
+    // existing reward assets cannot be dropped or replaced
+    let update_config = UpdateConfig {
+        guardian: None,
+        boost_curve: None,
+        reject_underfunded_schedules: None,
+        schedule_timelock: None,
+        rewards: Some(vec![
+            RewardAsset {
+                info: AssetInfo::Token {
+                    contract_addr: "reward0001".to_string(),
+                },
+                distribution_schedule: vec![],
+            },
+            RewardAsset {
+                info: AssetInfo::Token {
+                    contract_addr: "reward0000".to_string(),
+                },
+                distribution_schedule: vec![],
+            },
+        ]),
     };
-
-    // unauthorized attempt
-    let info = mock_info("notgov0000", &[]);
-    let res = execute(deps.as_mut(), env.clone(), info, msg.clone());
+    let info = mock_info("gov0000", &[]);
+    let res = execute(deps.as_mut(), mock_env(), info, update_config);
     match res {
-        Err(StdError::GenericErr { msg, .. }) => assert_eq!(msg, "unauthorized"),
-        _ => panic!("Must return unauthorized error"),
+        Err(StdError::GenericErr { msg, .. }) => {
+            assert_eq!(
+                msg,
+                "cannot update; existing reward assets cannot be removed"
+            )
+        }
+        _ => panic!("Must return generic error"),
     }
+}
 
-    // successful attempt
-    let info = mock_info("gov0000", &[]);
-    let res = execute(deps.as_mut(), env, info, msg).unwrap();
+#[test]
+fn test_migrate_single_reward() {
+    let mut deps = mock_dependencies(&[]);
+
+    singleton(&mut deps.storage, b"config")
+        .save(&LegacyConfig {
+            reward_token: deps.api.addr_canonicalize("reward0000").unwrap(),
+            staking_token: deps.api.addr_canonicalize("staking0000").unwrap(),
+            distribution_schedule: vec![(100, 200, Uint128::from(1000000u128))],
+            governance: deps.api.addr_canonicalize("gov0000").unwrap(),
+            unbonding_period: 0,
+        })
+        .unwrap();
+    singleton(&mut deps.storage, b"state")
+        .save(&LegacyState {
+            last_distributed: 150,
+            total_bond_amount: Uint128::from(100u128),
+            global_reward_index: Decimal::from_ratio(5000u128, 1u128),
+        })
+        .unwrap();
+    let staker_raw = deps.api.addr_canonicalize("addr0000").unwrap();
+    Bucket::new(&mut deps.storage, b"reward")
+        .save(
+            staker_raw.as_slice(),
+            &LegacyStakerInfo {
+                reward_index: Decimal::from_ratio(1000u128, 1u128),
+                bond_amount: Uint128::from(100u128),
+                pending_reward: Uint128::from(100000u128),
+            },
+        )
+        .unwrap();
+
+    let _res = migrate(
+        deps.as_mut(),
+        mock_env(),
+        MigrateMsg {
+            seconds_per_block: None,
+        },
+    )
+    .unwrap();
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap();
+    let config: ConfigResponse = from_binary(&res).unwrap();
+    assert_eq!(
+        config.rewards,
+        vec![RewardAsset {
+            info: AssetInfo::Token {
+                contract_addr: "reward0000".to_string()
+            },
+            distribution_schedule: vec![DistributionSchedule {
+                start_time: 100,
+                end_time: 200,
+                amount: Uint128::from(1000000u128)
+            }],
+        }]
+    );
+
+    assert_eq!(
+        from_binary::<StakerInfoResponse>(
+            &query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::StakerInfo {
+                    staker: "addr0000".to_string(),
+                    block_time: None,
+                },
+            )
+            .unwrap()
+        )
+        .unwrap(),
+        StakerInfoResponse {
+            staker: "addr0000".to_string(),
+            reward_indexes: vec![(
+                "reward0000".to_string(),
+                Decimal256::from_ratio(1000u64, 1u64)
+            )],
+            pending_rewards: vec![("reward0000".to_string(), Uint128::from(100000u128))],
+            bond_amount: Uint128::from(100u128),
+            boost: Decimal::one(),
+            lock_end: 0,
+            auto_compound: false,
+        }
+    );
+
+    // migrating again leaves the converted storage untouched
+    let _res = migrate(
+        deps.as_mut(),
+        mock_env(),
+        MigrateMsg {
+            seconds_per_block: None,
+        },
+    )
+    .unwrap();
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap();
+    assert_eq!(from_binary::<ConfigResponse>(&res).unwrap(), config);
+}
+
+#[test]
+fn test_withdraw_native_reward() {
+    let mut deps = mock_dependencies_with_tax(&[]);
+    deps.querier.with_tax(
+        Decimal::percent(1),
+        &[(&"uusd".to_string(), &Uint128::from(1000000u128))],
+    );
+
+    let msg = InstantiateMsg {
+        staking_token: "staking0000".to_string(),
+        rewards: vec![RewardAsset {
+            info: AssetInfo::NativeToken {
+                denom: "uusd".to_string(),
+            },
+            distribution_schedule: vec![DistributionSchedule {
+                start_time: mock_env().block.time.seconds(),
+                end_time: mock_env().block.time.seconds() + 100,
+                amount: Uint128::from(1000000u128),
+            }],
+        }],
+        governance: "gov0000".to_string(),
+        guardian: None,
+        unbonding_period: 0,
+        boost_curve: vec![],
+        reject_underfunded_schedules: false,
+        schedule_timelock: 0,
+    };
+
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    // bond 100 tokens
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "addr0000".to_string(),
+        amount: Uint128::from(100u128),
+        msg: to_binary(&Cw20HookMsg::Bond {
+            lock_duration: None,
+            beneficiary: None,
+        })
+        .unwrap(),
+    });
+    let info = mock_info("staking0000", &[]);
+    let mut env = mock_env();
+    let _res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
 
+    // 100 seconds passed
+    // 1,000,000 uusd distributed
+    env.block.time = env.block.time.plus_seconds(100);
+
+    let info = mock_info("addr0000", &[]);
+    let res = execute(
+        deps.as_mut(),
+        env,
+        info,
+        ExecuteMsg::Withdraw {
+            staker: None,
+            recipient: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+            to_address: "addr0000".to_string(),
+            amount: vec![Coin {
+                denom: "uusd".to_string(),
+                amount: Uint128::from(990100u128),
+            }],
+        }))]
+    );
     assert_eq!(
         res.attributes,
         vec![
-            attr("action", "migrate_staking"),
-            attr("distributed_amount", "6000000"), // 1000000 + (10000000 / 2)
-            attr("remaining_amount", "5000000")    // 11,000,000 - 6000000
+            attr("action", "withdraw"),
+            attr("owner", "addr0000"),
+            attr("recipient", "addr0000"),
+            attr("reward_token", "uusd"),
+            attr("amount", "1000000"),
         ]
     );
+}
+
+#[test]
+fn test_migrate_staking() {
+    let mut deps = mock_dependencies(&[]);
+
+    let msg = InstantiateMsg {
+        staking_token: "staking0000".to_string(),
+        rewards: vec![RewardAsset {
+            info: AssetInfo::Token {
+                contract_addr: "reward0000".to_string(),
+            },
+            distribution_schedule: vec![
+                DistributionSchedule {
+                    start_time: mock_env().block.time.seconds(),
+                    end_time: mock_env().block.time.seconds() + 100,
+                    amount: Uint128::from(1000000u128),
+                },
+                DistributionSchedule {
+                    start_time: mock_env().block.time.seconds() + 100,
+                    end_time: mock_env().block.time.seconds() + 200,
+                    amount: Uint128::from(10000000u128),
+                },
+            ],
+        }],
+        governance: "gov0000".to_string(),
+        guardian: None,
+        unbonding_period: 0,
+        boost_curve: vec![],
+        reject_underfunded_schedules: false,
+        schedule_timelock: 0,
+    };
+
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    // bond 100 tokens
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "addr0000".to_string(),
+        amount: Uint128::from(100u128),
+        msg: to_binary(&Cw20HookMsg::Bond {
+            lock_duration: None,
+            beneficiary: None,
+        })
+        .unwrap(),
+    });
+    let info = mock_info("staking0000", &[]);
+    let mut env = mock_env();
+    let _res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+    // 100 seconds is passed
+    // 1,000,000 rewards distributed
+    env.block.time = env.block.time.plus_seconds(100);
+    let info = mock_info("addr0000", &[]);
+
+    let msg = ExecuteMsg::Withdraw {
+        staker: None,
+        recipient: None,
+    };
+    let res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
 
     assert_eq!(
         res.messages,
         vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
             contract_addr: "reward0000".to_string(),
             msg: to_binary(&Cw20ExecuteMsg::Transfer {
-                recipient: "newstaking0000".to_string(),
-                amount: Uint128::from(5000000u128),
+                recipient: "addr0000".to_string(),
+                amount: Uint128::from(1000000u128),
             })
             .unwrap(),
             funds: vec![],
         }))]
     );
 
+    // a funded slot that has not started when the contract migrates
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "partner0000".to_string(),
+        amount: Uint128::from(300u128),
+        msg: to_binary(&Cw20HookMsg::Fund {
+            start_time: env.block.time.seconds() + 100,
+            duration: 100,
+        })
+        .unwrap(),
+    });
+    let _res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("reward0000", &[]),
+        msg,
+    )
+    .unwrap();
+
+    // execute migration after 50 seconds
+    env.block.time = env.block.time.plus_seconds(50);
+
+    let msg = ExecuteMsg::MigrateStaking {
+        new_staking_contract: "newstaking0000".to_string(),
+    };
+
+    // unauthorized attempt
+    let info = mock_info("notgov0000", &[]);
+    let res = execute(deps.as_mut(), env.clone(), info, msg.clone());
+    match res {
+        Err(StdError::GenericErr { msg, .. }) => assert_eq!(msg, "unauthorized"),
+        _ => panic!("Must return unauthorized error"),
+    }
+
+    // successful attempt
+    let info = mock_info("gov0000", &[]);
+    let res = execute(deps.as_mut(), env, info, msg).unwrap();
+
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "migrate_staking"),
+            attr("reward_token", "reward0000"),
+            attr("distributed_amount", "6000000"), // 1000000 + (10000000 / 2)
+            attr("remaining_amount", "5000000"),   // 11,000,000 - 6000000
+            attr("refunded_amount", "300"),
+        ]
+    );
+
+    // the funded slot goes back to its funder, not to the new contract
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "reward0000".to_string(),
+                msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: "partner0000".to_string(),
+                    amount: Uint128::from(300u128),
+                })
+                .unwrap(),
+                funds: vec![],
+            })),
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "reward0000".to_string(),
+                msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: "newstaking0000".to_string(),
+                    amount: Uint128::from(5000000u128),
+                })
+                .unwrap(),
+                funds: vec![],
+            })),
+        ]
+    );
+
     // query config
     let res = query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap();
     let config: ConfigResponse = from_binary(&res).unwrap();
     assert_eq!(
         config,
         ConfigResponse {
-            reward_token: "reward0000".to_string(),
             staking_token: "staking0000".to_string(),
-            distribution_schedule: vec![
-                (
-                    mock_env().block.time.seconds(),
-                    mock_env().block.time.seconds() + 100,
-                    Uint128::from(1000000u128)
-                ),
-                (
-                    mock_env().block.time.seconds() + 100,
-                    mock_env().block.time.seconds() + 150,
-                    Uint128::from(5000000u128)
-                ), // slot was modified
-            ],
+            rewards: vec![RewardAsset {
+                info: AssetInfo::Token {
+                    contract_addr: "reward0000".to_string()
+                },
+                distribution_schedule: vec![
+                    DistributionSchedule {
+                        start_time: mock_env().block.time.seconds(),
+                        end_time: mock_env().block.time.seconds() + 100,
+                        amount: Uint128::from(1000000u128)
+                    },
+                    DistributionSchedule {
+                        start_time: mock_env().block.time.seconds() + 100,
+                        end_time: mock_env().block.time.seconds() + 150,
+                        amount: Uint128::from(5000000u128)
+                    }, // slot was modified
+                ],
+            }],
             governance: "gov0000".to_string(),
+            guardian: None,
+            unbonding_period: 0,
+            boost_curve: vec![],
+            reject_underfunded_schedules: false,
+            pending_governance: None,
+            schedule_timelock: 0,
+            migrated_to: Some("newstaking0000".to_string()),
+            funded_slots: vec![],
         }
     );
 }
@@ -560,48 +1425,65 @@ fn test_update_config() {
     let mut deps = mock_dependencies(&[]);
 
     let msg = InstantiateMsg {
-        reward_token: "reward0000".to_string(),
         staking_token: "staking0000".to_string(),
-        distribution_schedule: vec![
-            (
-                mock_env().block.time.seconds(),
-                mock_env().block.time.seconds() + 100,
-                Uint128::from(1000000u128),
-            ),
-            (
-                mock_env().block.time.seconds() + 100,
-                mock_env().block.time.seconds() + 200,
-                Uint128::from(10000000u128),
-            ),
-            (
-                mock_env().block.time.seconds() + 200,
-                mock_env().block.time.seconds() + 300,
-                Uint128::from(10000000u128),
-            ),
-            (
-                mock_env().block.time.seconds() + 300,
-                mock_env().block.time.seconds() + 400,
-                Uint128::from(10000000u128),
-            ),
-            (
-                mock_env().block.time.seconds() + 400,
-                mock_env().block.time.seconds() + 500,
-                Uint128::from(10000000u128),
-            ),
-        ],
+        rewards: vec![RewardAsset {
+            info: AssetInfo::Token {
+                contract_addr: "reward0000".to_string(),
+            },
+            distribution_schedule: vec![
+                DistributionSchedule {
+                    start_time: mock_env().block.time.seconds(),
+                    end_time: mock_env().block.time.seconds() + 100,
+                    amount: Uint128::from(1000000u128),
+                },
+                DistributionSchedule {
+                    start_time: mock_env().block.time.seconds() + 100,
+                    end_time: mock_env().block.time.seconds() + 200,
+                    amount: Uint128::from(10000000u128),
+                },
+                DistributionSchedule {
+                    start_time: mock_env().block.time.seconds() + 200,
+                    end_time: mock_env().block.time.seconds() + 300,
+                    amount: Uint128::from(10000000u128),
+                },
+                DistributionSchedule {
+                    start_time: mock_env().block.time.seconds() + 300,
+                    end_time: mock_env().block.time.seconds() + 400,
+                    amount: Uint128::from(10000000u128),
+                },
+                DistributionSchedule {
+                    start_time: mock_env().block.time.seconds() + 400,
+                    end_time: mock_env().block.time.seconds() + 500,
+                    amount: Uint128::from(10000000u128),
+                },
+            ],
+        }],
         governance: "gov0000".to_string(),
+        guardian: None,
+        unbonding_period: 0,
+        boost_curve: vec![],
+        reject_underfunded_schedules: false,
+        schedule_timelock: 0,
     };
 
     let info = mock_info("addr0000", &[]);
     let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
 
     let update_config = UpdateConfig {
-        governance: None,
-        distribution_schedule: Some(vec![(
-            mock_env().block.time.seconds() + 300,
-            mock_env().block.time.seconds() + 400,
-            Uint128::from(10000000u128),
-        )]),
+        guardian: None,
+        boost_curve: None,
+        reject_underfunded_schedules: None,
+        schedule_timelock: None,
+        rewards: Some(vec![RewardAsset {
+            info: AssetInfo::Token {
+                contract_addr: "reward0000".to_string(),
+            },
+            distribution_schedule: vec![DistributionSchedule {
+                start_time: mock_env().block.time.seconds() + 300,
+                end_time: mock_env().block.time.seconds() + 400,
+                amount: Uint128::from(10000000u128),
+            }],
+        }]),
     };
 
     let info = mock_info("notgov", &[]);
@@ -616,7 +1498,11 @@ fn test_update_config() {
     let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
         sender: "addr0000".to_string(),
         amount: Uint128::from(100u128),
-        msg: to_binary(&Cw20HookMsg::Bond {}).unwrap(),
+        msg: to_binary(&Cw20HookMsg::Bond {
+            lock_duration: None,
+            beneficiary: None,
+        })
+        .unwrap(),
     });
     let info = mock_info("staking0000", &[]);
     let mut env = mock_env();
@@ -627,7 +1513,10 @@ fn test_update_config() {
     env.block.time = env.block.time.plus_seconds(100);
     let info = mock_info("addr0000", &[]);
 
-    let msg = ExecuteMsg::Withdraw {};
+    let msg = ExecuteMsg::Withdraw {
+        staker: None,
+        recipient: None,
+    };
     let res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
     assert_eq!(
         res.messages,
@@ -643,34 +1532,42 @@ fn test_update_config() {
     );
 
     let update_config = UpdateConfig {
-        distribution_schedule: Some(vec![
-            (
-                mock_env().block.time.seconds(),
-                mock_env().block.time.seconds() + 100,
-                Uint128::from(5000000u128),
-            ),
-            (
-                mock_env().block.time.seconds() + 100,
-                mock_env().block.time.seconds() + 200,
-                Uint128::from(10000000u128),
-            ),
-            (
-                mock_env().block.time.seconds() + 200,
-                mock_env().block.time.seconds() + 300,
-                Uint128::from(10000000u128),
-            ),
-            (
-                mock_env().block.time.seconds() + 300,
-                mock_env().block.time.seconds() + 400,
-                Uint128::from(10000000u128),
-            ),
-            (
-                mock_env().block.time.seconds() + 400,
-                mock_env().block.time.seconds() + 500,
-                Uint128::from(10000000u128),
-            ),
-        ]),
-        governance: None,
+        guardian: None,
+        boost_curve: None,
+        reject_underfunded_schedules: None,
+        rewards: Some(vec![RewardAsset {
+            info: AssetInfo::Token {
+                contract_addr: "reward0000".to_string(),
+            },
+            distribution_schedule: vec![
+                DistributionSchedule {
+                    start_time: mock_env().block.time.seconds(),
+                    end_time: mock_env().block.time.seconds() + 100,
+                    amount: Uint128::from(5000000u128),
+                },
+                DistributionSchedule {
+                    start_time: mock_env().block.time.seconds() + 100,
+                    end_time: mock_env().block.time.seconds() + 200,
+                    amount: Uint128::from(10000000u128),
+                },
+                DistributionSchedule {
+                    start_time: mock_env().block.time.seconds() + 200,
+                    end_time: mock_env().block.time.seconds() + 300,
+                    amount: Uint128::from(10000000u128),
+                },
+                DistributionSchedule {
+                    start_time: mock_env().block.time.seconds() + 300,
+                    end_time: mock_env().block.time.seconds() + 400,
+                    amount: Uint128::from(10000000u128),
+                },
+                DistributionSchedule {
+                    start_time: mock_env().block.time.seconds() + 400,
+                    end_time: mock_env().block.time.seconds() + 500,
+                    amount: Uint128::from(10000000u128),
+                },
+            ],
+        }]),
+        schedule_timelock: None,
     };
 
     let info = mock_info("gov0000", &[]);
@@ -687,7 +1584,11 @@ fn test_update_config() {
     let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
         sender: "addr0000".to_string(),
         amount: Uint128::from(100u128),
-        msg: to_binary(&Cw20HookMsg::Bond {}).unwrap(),
+        msg: to_binary(&Cw20HookMsg::Bond {
+            lock_duration: None,
+            beneficiary: None,
+        })
+        .unwrap(),
     });
     let info = mock_info("staking0000", &[]);
     let _res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
@@ -698,39 +1599,50 @@ fn test_update_config() {
 
     let info = mock_info("addr0000", &[]);
 
-    let msg = ExecuteMsg::Withdraw {};
+    let msg = ExecuteMsg::Withdraw {
+        staker: None,
+        recipient: None,
+    };
     let _res = execute(deps.as_mut(), env, info, msg).unwrap();
 
     //cannot update previous scehdule
     let update_config = UpdateConfig {
-        distribution_schedule: Some(vec![
-            (
-                mock_env().block.time.seconds(),
-                mock_env().block.time.seconds() + 100,
-                Uint128::from(5000000u128),
-            ),
-            (
-                mock_env().block.time.seconds() + 100,
-                mock_env().block.time.seconds() + 200,
-                Uint128::from(10000000u128),
-            ),
-            (
-                mock_env().block.time.seconds() + 200,
-                mock_env().block.time.seconds() + 300,
-                Uint128::from(10000000u128),
-            ),
-            (
-                mock_env().block.time.seconds() + 300,
-                mock_env().block.time.seconds() + 400,
-                Uint128::from(10000000u128),
-            ),
-            (
-                mock_env().block.time.seconds() + 400,
-                mock_env().block.time.seconds() + 500,
-                Uint128::from(10000000u128),
-            ),
-        ]),
-        governance: None,
+        guardian: None,
+        boost_curve: None,
+        reject_underfunded_schedules: None,
+        rewards: Some(vec![RewardAsset {
+            info: AssetInfo::Token {
+                contract_addr: "reward0000".to_string(),
+            },
+            distribution_schedule: vec![
+                DistributionSchedule {
+                    start_time: mock_env().block.time.seconds(),
+                    end_time: mock_env().block.time.seconds() + 100,
+                    amount: Uint128::from(5000000u128),
+                },
+                DistributionSchedule {
+                    start_time: mock_env().block.time.seconds() + 100,
+                    end_time: mock_env().block.time.seconds() + 200,
+                    amount: Uint128::from(10000000u128),
+                },
+                DistributionSchedule {
+                    start_time: mock_env().block.time.seconds() + 200,
+                    end_time: mock_env().block.time.seconds() + 300,
+                    amount: Uint128::from(10000000u128),
+                },
+                DistributionSchedule {
+                    start_time: mock_env().block.time.seconds() + 300,
+                    end_time: mock_env().block.time.seconds() + 400,
+                    amount: Uint128::from(10000000u128),
+                },
+                DistributionSchedule {
+                    start_time: mock_env().block.time.seconds() + 400,
+                    end_time: mock_env().block.time.seconds() + 500,
+                    amount: Uint128::from(10000000u128),
+                },
+            ],
+        }]),
+        schedule_timelock: None,
     };
 
     let info = mock_info("gov0000", &[]);
@@ -744,34 +1656,42 @@ fn test_update_config() {
 
     //successful one
     let update_config = UpdateConfig {
-        distribution_schedule: Some(vec![
-            (
-                mock_env().block.time.seconds(),
-                mock_env().block.time.seconds() + 100,
-                Uint128::from(1000000u128),
-            ),
-            (
-                mock_env().block.time.seconds() + 100,
-                mock_env().block.time.seconds() + 200,
-                Uint128::from(10000000u128),
-            ),
-            (
-                mock_env().block.time.seconds() + 200,
-                mock_env().block.time.seconds() + 300,
-                Uint128::from(10000000u128),
-            ),
-            (
-                mock_env().block.time.seconds() + 300,
-                mock_env().block.time.seconds() + 400,
-                Uint128::from(20000000u128),
-            ),
-            (
-                mock_env().block.time.seconds() + 400,
-                mock_env().block.time.seconds() + 500,
-                Uint128::from(10000000u128),
-            ),
-        ]),
-        governance: None,
+        guardian: None,
+        boost_curve: None,
+        reject_underfunded_schedules: None,
+        rewards: Some(vec![RewardAsset {
+            info: AssetInfo::Token {
+                contract_addr: "reward0000".to_string(),
+            },
+            distribution_schedule: vec![
+                DistributionSchedule {
+                    start_time: mock_env().block.time.seconds(),
+                    end_time: mock_env().block.time.seconds() + 100,
+                    amount: Uint128::from(1000000u128),
+                },
+                DistributionSchedule {
+                    start_time: mock_env().block.time.seconds() + 100,
+                    end_time: mock_env().block.time.seconds() + 200,
+                    amount: Uint128::from(10000000u128),
+                },
+                DistributionSchedule {
+                    start_time: mock_env().block.time.seconds() + 200,
+                    end_time: mock_env().block.time.seconds() + 300,
+                    amount: Uint128::from(10000000u128),
+                },
+                DistributionSchedule {
+                    start_time: mock_env().block.time.seconds() + 300,
+                    end_time: mock_env().block.time.seconds() + 400,
+                    amount: Uint128::from(20000000u128),
+                },
+                DistributionSchedule {
+                    start_time: mock_env().block.time.seconds() + 400,
+                    end_time: mock_env().block.time.seconds() + 500,
+                    amount: Uint128::from(10000000u128),
+                },
+            ],
+        }]),
+        schedule_timelock: None,
     };
 
     let info = mock_info("gov0000", &[]);
@@ -783,66 +1703,74 @@ fn test_update_config() {
     let res = query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap();
     let config: ConfigResponse = from_binary(&res).unwrap();
     assert_eq!(
-        config.distribution_schedule,
+        config.rewards[0].distribution_schedule,
         vec![
-            (
-                mock_env().block.time.seconds(),
-                mock_env().block.time.seconds() + 100,
-                Uint128::from(1000000u128),
-            ),
-            (
-                mock_env().block.time.seconds() + 100,
-                mock_env().block.time.seconds() + 200,
-                Uint128::from(10000000u128),
-            ),
-            (
-                mock_env().block.time.seconds() + 200,
-                mock_env().block.time.seconds() + 300,
-                Uint128::from(10000000u128),
-            ),
-            (
-                mock_env().block.time.seconds() + 300,
-                mock_env().block.time.seconds() + 400,
-                Uint128::from(20000000u128),
-            ),
-            (
-                mock_env().block.time.seconds() + 400,
-                mock_env().block.time.seconds() + 500,
-                Uint128::from(10000000u128),
-            ),
+            DistributionSchedule {
+                start_time: mock_env().block.time.seconds(),
+                end_time: mock_env().block.time.seconds() + 100,
+                amount: Uint128::from(1000000u128)
+            },
+            DistributionSchedule {
+                start_time: mock_env().block.time.seconds() + 100,
+                end_time: mock_env().block.time.seconds() + 200,
+                amount: Uint128::from(10000000u128)
+            },
+            DistributionSchedule {
+                start_time: mock_env().block.time.seconds() + 200,
+                end_time: mock_env().block.time.seconds() + 300,
+                amount: Uint128::from(10000000u128)
+            },
+            DistributionSchedule {
+                start_time: mock_env().block.time.seconds() + 300,
+                end_time: mock_env().block.time.seconds() + 400,
+                amount: Uint128::from(20000000u128)
+            },
+            DistributionSchedule {
+                start_time: mock_env().block.time.seconds() + 400,
+                end_time: mock_env().block.time.seconds() + 500,
+                amount: Uint128::from(10000000u128)
+            },
         ]
     );
 
     //successful one
     let update_config = UpdateConfig {
-        distribution_schedule: Some(vec![
-            (
-                mock_env().block.time.seconds(),
-                mock_env().block.time.seconds() + 100,
-                Uint128::from(1000000u128),
-            ),
-            (
-                mock_env().block.time.seconds() + 100,
-                mock_env().block.time.seconds() + 200,
-                Uint128::from(10000000u128),
-            ),
-            (
-                mock_env().block.time.seconds() + 200,
-                mock_env().block.time.seconds() + 300,
-                Uint128::from(10000000u128),
-            ),
-            (
-                mock_env().block.time.seconds() + 300,
-                mock_env().block.time.seconds() + 400,
-                Uint128::from(20000000u128),
-            ),
-            (
-                mock_env().block.time.seconds() + 400,
-                mock_env().block.time.seconds() + 500,
-                Uint128::from(50000000u128),
-            ),
-        ]),
-        governance: None,
+        guardian: None,
+        boost_curve: None,
+        reject_underfunded_schedules: None,
+        rewards: Some(vec![RewardAsset {
+            info: AssetInfo::Token {
+                contract_addr: "reward0000".to_string(),
+            },
+            distribution_schedule: vec![
+                DistributionSchedule {
+                    start_time: mock_env().block.time.seconds(),
+                    end_time: mock_env().block.time.seconds() + 100,
+                    amount: Uint128::from(1000000u128),
+                },
+                DistributionSchedule {
+                    start_time: mock_env().block.time.seconds() + 100,
+                    end_time: mock_env().block.time.seconds() + 200,
+                    amount: Uint128::from(10000000u128),
+                },
+                DistributionSchedule {
+                    start_time: mock_env().block.time.seconds() + 200,
+                    end_time: mock_env().block.time.seconds() + 300,
+                    amount: Uint128::from(10000000u128),
+                },
+                DistributionSchedule {
+                    start_time: mock_env().block.time.seconds() + 300,
+                    end_time: mock_env().block.time.seconds() + 400,
+                    amount: Uint128::from(20000000u128),
+                },
+                DistributionSchedule {
+                    start_time: mock_env().block.time.seconds() + 400,
+                    end_time: mock_env().block.time.seconds() + 500,
+                    amount: Uint128::from(50000000u128),
+                },
+            ],
+        }]),
+        schedule_timelock: None,
     };
 
     let info = mock_info("gov0000", &[]);
@@ -854,65 +1782,73 @@ fn test_update_config() {
     let res = query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap();
     let config: ConfigResponse = from_binary(&res).unwrap();
     assert_eq!(
-        config.distribution_schedule,
+        config.rewards[0].distribution_schedule,
         vec![
-            (
-                mock_env().block.time.seconds(),
-                mock_env().block.time.seconds() + 100,
-                Uint128::from(1000000u128),
-            ),
-            (
-                mock_env().block.time.seconds() + 100,
-                mock_env().block.time.seconds() + 200,
-                Uint128::from(10000000u128),
-            ),
-            (
-                mock_env().block.time.seconds() + 200,
-                mock_env().block.time.seconds() + 300,
-                Uint128::from(10000000u128),
-            ),
-            (
-                mock_env().block.time.seconds() + 300,
-                mock_env().block.time.seconds() + 400,
-                Uint128::from(20000000u128),
-            ),
-            (
-                mock_env().block.time.seconds() + 400,
-                mock_env().block.time.seconds() + 500,
-                Uint128::from(50000000u128),
-            ),
+            DistributionSchedule {
+                start_time: mock_env().block.time.seconds(),
+                end_time: mock_env().block.time.seconds() + 100,
+                amount: Uint128::from(1000000u128)
+            },
+            DistributionSchedule {
+                start_time: mock_env().block.time.seconds() + 100,
+                end_time: mock_env().block.time.seconds() + 200,
+                amount: Uint128::from(10000000u128)
+            },
+            DistributionSchedule {
+                start_time: mock_env().block.time.seconds() + 200,
+                end_time: mock_env().block.time.seconds() + 300,
+                amount: Uint128::from(10000000u128)
+            },
+            DistributionSchedule {
+                start_time: mock_env().block.time.seconds() + 300,
+                end_time: mock_env().block.time.seconds() + 400,
+                amount: Uint128::from(20000000u128)
+            },
+            DistributionSchedule {
+                start_time: mock_env().block.time.seconds() + 400,
+                end_time: mock_env().block.time.seconds() + 500,
+                amount: Uint128::from(50000000u128)
+            },
         ]
     );
 
     let update_config = UpdateConfig {
-        distribution_schedule: Some(vec![
-            (
-                mock_env().block.time.seconds(),
-                mock_env().block.time.seconds() + 100,
-                Uint128::from(1000000u128),
-            ),
-            (
-                mock_env().block.time.seconds() + 100,
-                mock_env().block.time.seconds() + 200,
-                Uint128::from(10000000u128),
-            ),
-            (
-                mock_env().block.time.seconds() + 200,
-                mock_env().block.time.seconds() + 300,
-                Uint128::from(10000000u128),
-            ),
-            (
-                mock_env().block.time.seconds() + 300,
-                mock_env().block.time.seconds() + 400,
-                Uint128::from(90000000u128),
-            ),
-            (
-                mock_env().block.time.seconds() + 400,
-                mock_env().block.time.seconds() + 500,
-                Uint128::from(80000000u128),
-            ),
-        ]),
-        governance: None,
+        guardian: None,
+        boost_curve: None,
+        reject_underfunded_schedules: None,
+        rewards: Some(vec![RewardAsset {
+            info: AssetInfo::Token {
+                contract_addr: "reward0000".to_string(),
+            },
+            distribution_schedule: vec![
+                DistributionSchedule {
+                    start_time: mock_env().block.time.seconds(),
+                    end_time: mock_env().block.time.seconds() + 100,
+                    amount: Uint128::from(1000000u128),
+                },
+                DistributionSchedule {
+                    start_time: mock_env().block.time.seconds() + 100,
+                    end_time: mock_env().block.time.seconds() + 200,
+                    amount: Uint128::from(10000000u128),
+                },
+                DistributionSchedule {
+                    start_time: mock_env().block.time.seconds() + 200,
+                    end_time: mock_env().block.time.seconds() + 300,
+                    amount: Uint128::from(10000000u128),
+                },
+                DistributionSchedule {
+                    start_time: mock_env().block.time.seconds() + 300,
+                    end_time: mock_env().block.time.seconds() + 400,
+                    amount: Uint128::from(90000000u128),
+                },
+                DistributionSchedule {
+                    start_time: mock_env().block.time.seconds() + 400,
+                    end_time: mock_env().block.time.seconds() + 500,
+                    amount: Uint128::from(80000000u128),
+                },
+            ],
+        }]),
+        schedule_timelock: None,
     };
 
     let info = mock_info("gov0000", &[]);
@@ -924,70 +1860,78 @@ fn test_update_config() {
     let res = query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap();
     let config: ConfigResponse = from_binary(&res).unwrap();
     assert_eq!(
-        config.distribution_schedule,
+        config.rewards[0].distribution_schedule,
         vec![
-            (
-                mock_env().block.time.seconds(),
-                mock_env().block.time.seconds() + 100,
-                Uint128::from(1000000u128),
-            ),
-            (
-                mock_env().block.time.seconds() + 100,
-                mock_env().block.time.seconds() + 200,
-                Uint128::from(10000000u128),
-            ),
-            (
-                mock_env().block.time.seconds() + 200,
-                mock_env().block.time.seconds() + 300,
-                Uint128::from(10000000u128),
-            ),
-            (
-                mock_env().block.time.seconds() + 300,
-                mock_env().block.time.seconds() + 400,
-                Uint128::from(90000000u128),
-            ),
-            (
-                mock_env().block.time.seconds() + 400,
-                mock_env().block.time.seconds() + 500,
-                Uint128::from(80000000u128),
-            ),
+            DistributionSchedule {
+                start_time: mock_env().block.time.seconds(),
+                end_time: mock_env().block.time.seconds() + 100,
+                amount: Uint128::from(1000000u128)
+            },
+            DistributionSchedule {
+                start_time: mock_env().block.time.seconds() + 100,
+                end_time: mock_env().block.time.seconds() + 200,
+                amount: Uint128::from(10000000u128)
+            },
+            DistributionSchedule {
+                start_time: mock_env().block.time.seconds() + 200,
+                end_time: mock_env().block.time.seconds() + 300,
+                amount: Uint128::from(10000000u128)
+            },
+            DistributionSchedule {
+                start_time: mock_env().block.time.seconds() + 300,
+                end_time: mock_env().block.time.seconds() + 400,
+                amount: Uint128::from(90000000u128)
+            },
+            DistributionSchedule {
+                start_time: mock_env().block.time.seconds() + 400,
+                end_time: mock_env().block.time.seconds() + 500,
+                amount: Uint128::from(80000000u128)
+            },
         ]
     );
 
     let update_config = UpdateConfig {
-        distribution_schedule: Some(vec![
-            (
-                mock_env().block.time.seconds(),
-                mock_env().block.time.seconds() + 100,
-                Uint128::from(1000000u128),
-            ),
-            (
-                mock_env().block.time.seconds() + 100,
-                mock_env().block.time.seconds() + 200,
-                Uint128::from(10000000u128),
-            ),
-            (
-                mock_env().block.time.seconds() + 200,
-                mock_env().block.time.seconds() + 300,
-                Uint128::from(10000000u128),
-            ),
-            (
-                mock_env().block.time.seconds() + 300,
-                mock_env().block.time.seconds() + 400,
-                Uint128::from(90000000u128),
-            ),
-            (
-                mock_env().block.time.seconds() + 400,
-                mock_env().block.time.seconds() + 500,
-                Uint128::from(80000000u128),
-            ),
-            (
-                mock_env().block.time.seconds() + 500,
-                mock_env().block.time.seconds() + 600,
-                Uint128::from(60000000u128),
-            ),
-        ]),
-        governance: Some("gov0001".to_string()),
+        guardian: None,
+        boost_curve: None,
+        reject_underfunded_schedules: None,
+        rewards: Some(vec![RewardAsset {
+            info: AssetInfo::Token {
+                contract_addr: "reward0000".to_string(),
+            },
+            distribution_schedule: vec![
+                DistributionSchedule {
+                    start_time: mock_env().block.time.seconds(),
+                    end_time: mock_env().block.time.seconds() + 100,
+                    amount: Uint128::from(1000000u128),
+                },
+                DistributionSchedule {
+                    start_time: mock_env().block.time.seconds() + 100,
+                    end_time: mock_env().block.time.seconds() + 200,
+                    amount: Uint128::from(10000000u128),
+                },
+                DistributionSchedule {
+                    start_time: mock_env().block.time.seconds() + 200,
+                    end_time: mock_env().block.time.seconds() + 300,
+                    amount: Uint128::from(10000000u128),
+                },
+                DistributionSchedule {
+                    start_time: mock_env().block.time.seconds() + 300,
+                    end_time: mock_env().block.time.seconds() + 400,
+                    amount: Uint128::from(90000000u128),
+                },
+                DistributionSchedule {
+                    start_time: mock_env().block.time.seconds() + 400,
+                    end_time: mock_env().block.time.seconds() + 500,
+                    amount: Uint128::from(80000000u128),
+                },
+                DistributionSchedule {
+                    start_time: mock_env().block.time.seconds() + 500,
+                    end_time: mock_env().block.time.seconds() + 600,
+                    amount: Uint128::from(60000000u128),
+                },
+            ],
+        }]),
+        schedule_timelock: None,
     };
 
     let info = mock_info("gov0000", &[]);
@@ -999,39 +1943,2534 @@ fn test_update_config() {
     let res = query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap();
     let config: ConfigResponse = from_binary(&res).unwrap();
     assert_eq!(
-        config.distribution_schedule,
+        config.rewards[0].distribution_schedule,
         vec![
-            (
-                mock_env().block.time.seconds(),
-                mock_env().block.time.seconds() + 100,
-                Uint128::from(1000000u128),
-            ),
-            (
-                mock_env().block.time.seconds() + 100,
-                mock_env().block.time.seconds() + 200,
-                Uint128::from(10000000u128),
-            ),
-            (
-                mock_env().block.time.seconds() + 200,
-                mock_env().block.time.seconds() + 300,
-                Uint128::from(10000000u128),
-            ),
-            (
-                mock_env().block.time.seconds() + 300,
-                mock_env().block.time.seconds() + 400,
-                Uint128::from(90000000u128),
-            ),
-            (
-                mock_env().block.time.seconds() + 400,
-                mock_env().block.time.seconds() + 500,
-                Uint128::from(80000000u128),
-            ),
-            (
-                mock_env().block.time.seconds() + 500,
-                mock_env().block.time.seconds() + 600,
-                Uint128::from(60000000u128),
+            DistributionSchedule {
+                start_time: mock_env().block.time.seconds(),
+                end_time: mock_env().block.time.seconds() + 100,
+                amount: Uint128::from(1000000u128)
+            },
+            DistributionSchedule {
+                start_time: mock_env().block.time.seconds() + 100,
+                end_time: mock_env().block.time.seconds() + 200,
+                amount: Uint128::from(10000000u128)
+            },
+            DistributionSchedule {
+                start_time: mock_env().block.time.seconds() + 200,
+                end_time: mock_env().block.time.seconds() + 300,
+                amount: Uint128::from(10000000u128)
+            },
+            DistributionSchedule {
+                start_time: mock_env().block.time.seconds() + 300,
+                end_time: mock_env().block.time.seconds() + 400,
+                amount: Uint128::from(90000000u128)
+            },
+            DistributionSchedule {
+                start_time: mock_env().block.time.seconds() + 400,
+                end_time: mock_env().block.time.seconds() + 500,
+                amount: Uint128::from(80000000u128)
+            },
+            DistributionSchedule {
+                start_time: mock_env().block.time.seconds() + 500,
+                end_time: mock_env().block.time.seconds() + 600,
+                amount: Uint128::from(60000000u128)
+            }
+        ]
+    );
+}
+
+#[test]
+fn test_bond_with_lock_boost() {
+    let mut deps = mock_dependencies(&[]);
+
+    let msg = InstantiateMsg {
+        staking_token: "staking0000".to_string(),
+        rewards: vec![RewardAsset {
+            info: AssetInfo::Token {
+                contract_addr: "reward0000".to_string(),
+            },
+            distribution_schedule: vec![DistributionSchedule {
+                start_time: mock_env().block.time.seconds(),
+                end_time: mock_env().block.time.seconds() + 100,
+                amount: Uint128::from(1000000u128),
+            }],
+        }],
+        governance: "gov0000".to_string(),
+        guardian: None,
+        unbonding_period: 0,
+        boost_curve: vec![(100, Decimal::percent(150)), (200, Decimal::percent(200))],
+        reject_underfunded_schedules: false,
+        schedule_timelock: 0,
+    };
+
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    // lock longer than the curve allows
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "addr0000".to_string(),
+        amount: Uint128::from(100u128),
+        msg: to_binary(&Cw20HookMsg::Bond {
+            lock_duration: Some(300),
+            beneficiary: None,
+        })
+        .unwrap(),
+    });
+    let info = mock_info("staking0000", &[]);
+    let res = execute(deps.as_mut(), mock_env(), info.clone(), msg);
+    match res {
+        Err(StdError::GenericErr { msg, .. }) => assert_eq!(
+            msg,
+            "lock duration exceeds the longest lock of the boost curve"
+        ),
+        _ => panic!("Must return generic error"),
+    }
+
+    // addr0000 locks for 200 seconds (2x), addr0001 does not lock
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "addr0000".to_string(),
+        amount: Uint128::from(100u128),
+        msg: to_binary(&Cw20HookMsg::Bond {
+            lock_duration: Some(200),
+            beneficiary: None,
+        })
+        .unwrap(),
+    });
+    let _res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "addr0001".to_string(),
+        amount: Uint128::from(100u128),
+        msg: to_binary(&Cw20HookMsg::Bond {
+            lock_duration: None,
+            beneficiary: None,
+        })
+        .unwrap(),
+    });
+    let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    // 300000 distributed over a total weight of 300
+    let mut env = mock_env();
+    env.block.time = env.block.time.plus_seconds(30);
+    assert_eq!(
+        from_binary::<StakerInfoResponse>(
+            &query(
+                deps.as_ref(),
+                env.clone(),
+                QueryMsg::StakerInfo {
+                    staker: "addr0000".to_string(),
+                    block_time: Some(env.block.time.seconds()),
+                },
+            )
+            .unwrap()
+        )
+        .unwrap(),
+        StakerInfoResponse {
+            staker: "addr0000".to_string(),
+            reward_indexes: vec![(
+                "reward0000".to_string(),
+                Decimal256::from_ratio(1000u64, 1u64)
+            )],
+            pending_rewards: vec![("reward0000".to_string(), Uint128::from(200000u128))],
+            bond_amount: Uint128::from(100u128),
+            boost: Decimal::percent(200),
+            lock_end: mock_env().block.time.seconds() + 200,
+            auto_compound: false,
+        }
+    );
+
+    // locked stake cannot be unbonded
+    let info = mock_info("addr0000", &[]);
+    let msg = ExecuteMsg::Unbond {
+        amount: Uint128::from(100u128),
+    };
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg);
+    match res {
+        Err(StdError::GenericErr { msg, .. }) => {
+            assert_eq!(msg, "Cannot unbond before the lock ends")
+        }
+        _ => panic!("Must return generic error"),
+    }
+
+    let msg = ExecuteMsg::ExpireLock {
+        staker: "addr0000".to_string(),
+    };
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg.clone());
+    match res {
+        Err(StdError::GenericErr { msg, .. }) => assert_eq!(msg, "No ended lock to expire"),
+        _ => panic!("Must return generic error"),
+    }
+
+    // anyone can expire the lock once it has ended
+    env.block.time = env.block.time.plus_seconds(170);
+    let _res = execute(deps.as_mut(), env.clone(), mock_info("addr0001", &[]), msg).unwrap();
+    assert_eq!(
+        from_binary::<StateResponse>(
+            &query(
+                deps.as_ref(),
+                env.clone(),
+                QueryMsg::State { block_time: None }
             )
+            .unwrap()
+        )
+        .unwrap(),
+        StateResponse {
+            last_distributed: env.block.time.seconds(),
+            total_bond_amount: Uint128::from(200u128),
+            total_weight: Uint128::from(200u128),
+            global_reward_indexes: vec![(
+                "reward0000".to_string(),
+                Decimal256::from_ratio(10000u64, 3u64)
+            )],
+            share_price: Decimal256::one(),
+        }
+    );
+
+    let msg = ExecuteMsg::Unbond {
+        amount: Uint128::from(100u128),
+    };
+    let _res = execute(deps.as_mut(), env, info, msg).unwrap();
+}
+
+#[test]
+fn test_compound() {
+    let mut deps = mock_dependencies(&[]);
+
+    let msg = InstantiateMsg {
+        staking_token: "staking0000".to_string(),
+        rewards: vec![RewardAsset {
+            info: AssetInfo::Token {
+                contract_addr: "reward0000".to_string(),
+            },
+            distribution_schedule: vec![DistributionSchedule {
+                start_time: mock_env().block.time.seconds(),
+                end_time: mock_env().block.time.seconds() + 100,
+                amount: Uint128::from(1000000u128),
+            }],
+        }],
+        governance: "gov0000".to_string(),
+        guardian: None,
+        unbonding_period: 0,
+        boost_curve: vec![],
+        reject_underfunded_schedules: false,
+        schedule_timelock: 0,
+    };
+
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+    // reward token differs from the staking token
+    let msg = ExecuteMsg::SetAutoCompound {
+        auto_compound: true,
+    };
+    let res = execute(deps.as_mut(), mock_env(), info.clone(), msg);
+    match res {
+        Err(StdError::GenericErr { msg, .. }) => assert_eq!(
+            msg,
+            "cannot compound; no reward asset matches the staking token"
+        ),
+        _ => panic!("Must return generic error"),
+    }
+
+    let mut deps = mock_dependencies(&[]);
+    let msg = InstantiateMsg {
+        staking_token: "staking0000".to_string(),
+        rewards: vec![
+            RewardAsset {
+                info: AssetInfo::Token {
+                    contract_addr: "reward0000".to_string(),
+                },
+                distribution_schedule: vec![DistributionSchedule {
+                    start_time: mock_env().block.time.seconds(),
+                    end_time: mock_env().block.time.seconds() + 100,
+                    amount: Uint128::from(1000000u128),
+                }],
+            },
+            RewardAsset {
+                info: AssetInfo::Token {
+                    contract_addr: "staking0000".to_string(),
+                },
+                distribution_schedule: vec![DistributionSchedule {
+                    start_time: mock_env().block.time.seconds(),
+                    end_time: mock_env().block.time.seconds() + 100,
+                    amount: Uint128::from(2000000u128),
+                }],
+            },
+        ],
+        governance: "gov0000".to_string(),
+        guardian: None,
+        unbonding_period: 0,
+        boost_curve: vec![],
+        reject_underfunded_schedules: false,
+        schedule_timelock: 0,
+    };
+    let _res = instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "addr0000".to_string(),
+        amount: Uint128::from(100u128),
+        msg: to_binary(&Cw20HookMsg::Bond {
+            lock_duration: None,
+            beneficiary: None,
+        })
+        .unwrap(),
+    });
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("staking0000", &[]),
+        msg,
+    )
+    .unwrap();
+
+    // 50 seconds pass; half of the staking token reward is compounded
+    let mut env = mock_env();
+    env.block.time = env.block.time.plus_seconds(50);
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        ExecuteMsg::Compound {},
+    )
+    .unwrap();
+    assert_eq!(res.messages, vec![]);
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "compound"),
+            attr("owner", "addr0000"),
+            attr("amount", "1000000"),
         ]
     );
-    assert_eq!(config.governance, "gov0001".to_string());
+
+    let msg = ExecuteMsg::SetAutoCompound {
+        auto_compound: true,
+    };
+    let _res = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+    // withdraw compounds the staking token reward and pays out the rest,
+    // less rounding dust of the larger bond
+    env.block.time = env.block.time.plus_seconds(50);
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        info,
+        ExecuteMsg::Withdraw {
+            staker: None,
+            recipient: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "reward0000".to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: "addr0000".to_string(),
+                amount: Uint128::from(999999u128),
+            })
+            .unwrap(),
+            funds: vec![],
+        }))]
+    );
+
+    let staker_info = from_binary::<StakerInfoResponse>(
+        &query(
+            deps.as_ref(),
+            env,
+            QueryMsg::StakerInfo {
+                staker: "addr0000".to_string(),
+                block_time: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(staker_info.bond_amount, Uint128::from(2000099u128));
+    assert!(staker_info.auto_compound);
+    assert_eq!(
+        staker_info.pending_rewards,
+        vec![
+            ("reward0000".to_string(), Uint128::zero()),
+            ("staking0000".to_string(), Uint128::zero()),
+        ]
+    );
+}
+
+#[test]
+fn test_withdraw_by_operator() {
+    let mut deps = mock_dependencies(&[]);
+
+    let msg = InstantiateMsg {
+        staking_token: "staking0000".to_string(),
+        rewards: vec![RewardAsset {
+            info: AssetInfo::Token {
+                contract_addr: "reward0000".to_string(),
+            },
+            distribution_schedule: vec![DistributionSchedule {
+                start_time: mock_env().block.time.seconds(),
+                end_time: mock_env().block.time.seconds() + 100,
+                amount: Uint128::from(1000000u128),
+            }],
+        }],
+        governance: "gov0000".to_string(),
+        guardian: None,
+        unbonding_period: 0,
+        boost_curve: vec![],
+        reject_underfunded_schedules: false,
+        schedule_timelock: 0,
+    };
+
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "addr0000".to_string(),
+        amount: Uint128::from(100u128),
+        msg: to_binary(&Cw20HookMsg::Bond {
+            lock_duration: None,
+            beneficiary: None,
+        })
+        .unwrap(),
+    });
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("staking0000", &[]),
+        msg,
+    )
+    .unwrap();
+
+    let mut env = mock_env();
+    env.block.time = env.block.time.plus_seconds(10);
+
+    // not approved yet
+    let keeper_info = mock_info("keeper0000", &[]);
+    let msg = ExecuteMsg::Withdraw {
+        staker: Some("addr0000".to_string()),
+        recipient: None,
+    };
+    let res = execute(deps.as_mut(), env.clone(), keeper_info.clone(), msg.clone());
+    match res {
+        Err(StdError::GenericErr { msg, .. }) => assert_eq!(msg, "unauthorized"),
+        _ => panic!("Must return generic error"),
+    }
+
+    let _res = execute(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        ExecuteMsg::ApproveOperator {
+            operator: "keeper0000".to_string(),
+        },
+    )
+    .unwrap();
+
+    // operator cannot pay an address the staker has not whitelisted
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        keeper_info.clone(),
+        ExecuteMsg::Withdraw {
+            staker: Some("addr0000".to_string()),
+            recipient: Some("custody0000".to_string()),
+        },
+    );
+    match res {
+        Err(StdError::GenericErr { msg, .. }) => {
+            assert_eq!(msg, "recipient is not whitelisted by the staker")
+        }
+        _ => panic!("Must return generic error"),
+    }
+
+    let _res = execute(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        ExecuteMsg::AddRecipient {
+            recipient: "custody0000".to_string(),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        from_binary::<WithdrawPermissionsResponse>(
+            &query(
+                deps.as_ref(),
+                env.clone(),
+                QueryMsg::WithdrawPermissions {
+                    staker: "addr0000".to_string(),
+                },
+            )
+            .unwrap()
+        )
+        .unwrap(),
+        WithdrawPermissionsResponse {
+            staker: "addr0000".to_string(),
+            operators: vec!["keeper0000".to_string()],
+            recipients: vec!["custody0000".to_string()],
+        }
+    );
+
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        keeper_info.clone(),
+        ExecuteMsg::Withdraw {
+            staker: Some("addr0000".to_string()),
+            recipient: Some("custody0000".to_string()),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "reward0000".to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: "custody0000".to_string(),
+                amount: Uint128::from(100000u128),
+            })
+            .unwrap(),
+            funds: vec![],
+        }))]
+    );
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "withdraw"),
+            attr("owner", "addr0000"),
+            attr("recipient", "custody0000"),
+            attr("reward_token", "reward0000"),
+            attr("amount", "100000"),
+        ]
+    );
+
+    // revoked operators lose access
+    let _res = execute(
+        deps.as_mut(),
+        env.clone(),
+        info,
+        ExecuteMsg::RevokeOperator {
+            operator: "keeper0000".to_string(),
+        },
+    )
+    .unwrap();
+    let res = execute(deps.as_mut(), env, keeper_info, msg);
+    match res {
+        Err(StdError::GenericErr { msg, .. }) => assert_eq!(msg, "unauthorized"),
+        _ => panic!("Must return generic error"),
+    }
+}
+
+#[test]
+fn test_bond_for_beneficiary() {
+    let mut deps = mock_dependencies(&[]);
+
+    let msg = InstantiateMsg {
+        staking_token: "staking0000".to_string(),
+        rewards: vec![RewardAsset {
+            info: AssetInfo::Token {
+                contract_addr: "reward0000".to_string(),
+            },
+            distribution_schedule: vec![DistributionSchedule {
+                start_time: mock_env().block.time.seconds(),
+                end_time: mock_env().block.time.seconds() + 100,
+                amount: Uint128::from(1000000u128),
+            }],
+        }],
+        governance: "gov0000".to_string(),
+        guardian: None,
+        unbonding_period: 0,
+        boost_curve: vec![(100, Decimal::percent(150))],
+        reject_underfunded_schedules: false,
+        schedule_timelock: 0,
+    };
+
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    // a router cannot lock the bond of the beneficiary
+    let info = mock_info("staking0000", &[]);
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "router0000".to_string(),
+        amount: Uint128::from(100u128),
+        msg: to_binary(&Cw20HookMsg::Bond {
+            lock_duration: Some(100),
+            beneficiary: Some("addr0000".to_string()),
+        })
+        .unwrap(),
+    });
+    let res = execute(deps.as_mut(), mock_env(), info.clone(), msg);
+    match res {
+        Err(StdError::GenericErr { msg, .. }) => {
+            assert_eq!(msg, "cannot lock a bond on behalf of another address")
+        }
+        _ => panic!("Must return generic error"),
+    }
+
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "router0000".to_string(),
+        amount: Uint128::from(100u128),
+        msg: to_binary(&Cw20HookMsg::Bond {
+            lock_duration: None,
+            beneficiary: Some("addr0000".to_string()),
+        })
+        .unwrap(),
+    });
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "bond"),
+            attr("payer", "router0000"),
+            attr("beneficiary", "addr0000"),
+            attr("amount", "100"),
+            attr("lock_end", "0"),
+        ]
+    );
+
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::AllStakers {
+            start_after: None,
+            limit: None,
+            block_time: None,
+        },
+    )
+    .unwrap();
+    let stakers = from_binary::<AllStakersResponse>(&res).unwrap().stakers;
+    assert_eq!(stakers.len(), 1);
+    assert_eq!(stakers[0].staker, "addr0000".to_string());
+    assert_eq!(stakers[0].bond_amount, Uint128::from(100u128));
+}
+
+#[test]
+fn test_transfer_position() {
+    let mut deps = mock_dependencies(&[]);
+
+    let msg = InstantiateMsg {
+        staking_token: "staking0000".to_string(),
+        rewards: vec![RewardAsset {
+            info: AssetInfo::Token {
+                contract_addr: "reward0000".to_string(),
+            },
+            distribution_schedule: vec![DistributionSchedule {
+                start_time: mock_env().block.time.seconds(),
+                end_time: mock_env().block.time.seconds() + 100,
+                amount: Uint128::from(1000000u128),
+            }],
+        }],
+        governance: "gov0000".to_string(),
+        guardian: None,
+        unbonding_period: 0,
+        boost_curve: vec![],
+        reject_underfunded_schedules: false,
+        schedule_timelock: 0,
+    };
+
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "addr0000".to_string(),
+        amount: Uint128::from(100u128),
+        msg: to_binary(&Cw20HookMsg::Bond {
+            lock_duration: None,
+            beneficiary: None,
+        })
+        .unwrap(),
+    });
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("staking0000", &[]),
+        msg,
+    )
+    .unwrap();
+
+    let mut env = mock_env();
+    env.block.time = env.block.time.plus_seconds(10);
+
+    let msg = ExecuteMsg::TransferPosition {
+        recipient: "addr0001".to_string(),
+        amount: Uint128::from(101u128),
+    };
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg);
+    match res {
+        Err(StdError::GenericErr { msg, .. }) => {
+            assert_eq!(msg, "Cannot transfer more than bond amount")
+        }
+        _ => panic!("Must return generic error"),
+    }
+
+    let msg = ExecuteMsg::TransferPosition {
+        recipient: "addr0001".to_string(),
+        amount: Uint128::from(60u128),
+    };
+    let res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+    assert_eq!(res.messages, vec![]);
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "transfer_position"),
+            attr("owner", "addr0000"),
+            attr("recipient", "addr0001"),
+            attr("amount", "60"),
+        ]
+    );
+
+    // rewards earned before the transfer stay with the sender
+    env.block.time = env.block.time.plus_seconds(10);
+    let res = query(
+        deps.as_ref(),
+        env.clone(),
+        QueryMsg::AllStakers {
+            start_after: None,
+            limit: None,
+            block_time: Some(env.block.time.seconds()),
+        },
+    )
+    .unwrap();
+    let stakers = from_binary::<AllStakersResponse>(&res).unwrap().stakers;
+    assert_eq!(
+        stakers
+            .iter()
+            .map(|staker| (
+                staker.staker.clone(),
+                staker.bond_amount,
+                staker.pending_rewards[0].1
+            ))
+            .collect::<Vec<_>>(),
+        vec![
+            (
+                "addr0000".to_string(),
+                Uint128::from(40u128),
+                Uint128::from(140000u128)
+            ),
+            (
+                "addr0001".to_string(),
+                Uint128::from(60u128),
+                Uint128::from(60000u128)
+            ),
+        ]
+    );
+
+    let res = query(deps.as_ref(), env, QueryMsg::State { block_time: None }).unwrap();
+    let state = from_binary::<StateResponse>(&res).unwrap();
+    assert_eq!(state.total_bond_amount, Uint128::from(100u128));
+    assert_eq!(state.total_weight, Uint128::from(100u128));
+}
+
+#[test]
+fn test_pause_and_emergency_unbond() {
+    let mut deps = mock_dependencies(&[]);
+
+    let msg = InstantiateMsg {
+        staking_token: "staking0000".to_string(),
+        rewards: vec![RewardAsset {
+            info: AssetInfo::Token {
+                contract_addr: "reward0000".to_string(),
+            },
+            distribution_schedule: vec![DistributionSchedule {
+                start_time: mock_env().block.time.seconds(),
+                end_time: mock_env().block.time.seconds() + 100,
+                amount: Uint128::from(1000000u128),
+            }],
+        }],
+        governance: "gov0000".to_string(),
+        guardian: Some("guardian0000".to_string()),
+        unbonding_period: 100,
+        boost_curve: vec![],
+        reject_underfunded_schedules: false,
+        schedule_timelock: 0,
+    };
+
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "addr0000".to_string(),
+        amount: Uint128::from(100u128),
+        msg: to_binary(&Cw20HookMsg::Bond {
+            lock_duration: None,
+            beneficiary: None,
+        })
+        .unwrap(),
+    });
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("staking0000", &[]),
+        msg,
+    )
+    .unwrap();
+    let msg = ExecuteMsg::Unbond {
+        amount: Uint128::from(40u128),
+    };
+    let _res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+    // emergency exit needs unbond to be paused
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        info.clone(),
+        ExecuteMsg::EmergencyUnbond {},
+    );
+    match res {
+        Err(StdError::GenericErr { msg, .. }) => assert_eq!(
+            msg,
+            "emergency unbond is only available while unbond is paused"
+        ),
+        _ => panic!("Must return generic error"),
+    }
+
+    let msg = ExecuteMsg::Pause {
+        operation: Operation::Unbond,
+    };
+    let res = execute(deps.as_mut(), mock_env(), info.clone(), msg.clone());
+    match res {
+        Err(StdError::GenericErr { msg, .. }) => assert_eq!(msg, "unauthorized"),
+        _ => panic!("Must return generic error"),
+    }
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("guardian0000", &[]),
+        msg,
+    )
+    .unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![attr("action", "pause"), attr("operation", "unbond")]
+    );
+    assert_eq!(
+        from_binary::<PauseStatusResponse>(
+            &query(deps.as_ref(), mock_env(), QueryMsg::PauseStatus {}).unwrap()
+        )
+        .unwrap(),
+        PauseStatusResponse {
+            bond: false,
+            unbond: true,
+            withdraw: false,
+            migrate_staking: false,
+            emergency_unbond: false,
+        }
+    );
+
+    let msg = ExecuteMsg::Unbond {
+        amount: Uint128::from(10u128),
+    };
+    let res = execute(deps.as_mut(), mock_env(), info.clone(), msg);
+    match res {
+        Err(StdError::GenericErr { msg, .. }) => assert_eq!(msg, "operation is paused"),
+        _ => panic!("Must return generic error"),
+    }
+
+    // pausing alone does not let stakers skip locks and unbonding
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        info.clone(),
+        ExecuteMsg::EmergencyUnbond {},
+    );
+    match res {
+        Err(StdError::GenericErr { msg, .. }) => {
+            assert_eq!(msg, "emergency unbond is not enabled by governance")
+        }
+        _ => panic!("Must return generic error"),
+    }
+
+    let msg = ExecuteMsg::SetEmergencyUnbond { enabled: true };
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("guardian0000", &[]),
+        msg.clone(),
+    );
+    match res {
+        Err(StdError::GenericErr { msg, .. }) => assert_eq!(msg, "unauthorized"),
+        _ => panic!("Must return generic error"),
+    }
+    let _res = execute(deps.as_mut(), mock_env(), mock_info("gov0000", &[]), msg).unwrap();
+
+    // bond and queued unbondings are returned at once
+    let mut env = mock_env();
+    env.block.time = env.block.time.plus_seconds(10);
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        ExecuteMsg::EmergencyUnbond {},
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "staking0000".to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: "addr0000".to_string(),
+                amount: Uint128::from(100u128),
+            })
+            .unwrap(),
+            funds: vec![],
+        }))]
+    );
+
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::State { block_time: None },
+    )
+    .unwrap();
+    let state = from_binary::<StateResponse>(&res).unwrap();
+    assert_eq!(state.total_bond_amount, Uint128::zero());
+    assert_eq!(state.total_weight, Uint128::zero());
+
+    // rewards of the last ten seconds stay with the staker
+    let res = query(
+        deps.as_ref(),
+        env,
+        QueryMsg::StakerInfo {
+            staker: "addr0000".to_string(),
+            block_time: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        from_binary::<StakerInfoResponse>(&res)
+            .unwrap()
+            .pending_rewards,
+        vec![("reward0000".to_string(), Uint128::from(99999u128))]
+    );
+
+    // only governance resumes operations
+    let msg = ExecuteMsg::Unpause {
+        operation: Operation::Unbond,
+    };
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("guardian0000", &[]),
+        msg.clone(),
+    );
+    match res {
+        Err(StdError::GenericErr { msg, .. }) => assert_eq!(msg, "unauthorized"),
+        _ => panic!("Must return generic error"),
+    }
+    let _res = execute(deps.as_mut(), mock_env(), mock_info("gov0000", &[]), msg).unwrap();
+    assert!(
+        !from_binary::<PauseStatusResponse>(
+            &query(deps.as_ref(), mock_env(), QueryMsg::PauseStatus {}).unwrap()
+        )
+        .unwrap()
+        .unbond
+    );
+}
+
+#[test]
+fn test_invalid_distribution_schedule() {
+    let mut deps = mock_dependencies(&[]);
+
+    let mut msg = InstantiateMsg {
+        staking_token: "staking0000".to_string(),
+        rewards: vec![RewardAsset {
+            info: AssetInfo::Token {
+                contract_addr: "reward0000".to_string(),
+            },
+            distribution_schedule: vec![DistributionSchedule {
+                start_time: mock_env().block.time.seconds(),
+                end_time: mock_env().block.time.seconds(),
+                amount: Uint128::from(1000000u128),
+            }],
+        }],
+        governance: "gov0000".to_string(),
+        guardian: None,
+        unbonding_period: 0,
+        boost_curve: vec![],
+        reject_underfunded_schedules: false,
+        schedule_timelock: 0,
+    };
+
+    let info = mock_info("addr0000", &[]);
+    let res = instantiate(deps.as_mut(), mock_env(), info.clone(), msg.clone());
+    match res {
+        Err(StdError::GenericErr { msg, .. }) => {
+            assert_eq!(msg, "distribution schedule must end after it starts")
+        }
+        _ => panic!("Must return generic error"),
+    }
+
+    msg.rewards[0].distribution_schedule[0].end_time = mock_env().block.time.seconds() + 100;
+    let _res = instantiate(deps.as_mut(), mock_env(), info, msg.clone()).unwrap();
+
+    // inverted slot
+    let mut rewards = msg.rewards;
+    rewards[0].distribution_schedule.push(DistributionSchedule {
+        start_time: mock_env().block.time.seconds() + 200,
+        end_time: mock_env().block.time.seconds() + 100,
+        amount: Uint128::from(1000000u128),
+    });
+    let update_config = UpdateConfig {
+        schedule_timelock: None,
+        guardian: None,
+        rewards: Some(rewards),
+        boost_curve: None,
+        reject_underfunded_schedules: None,
+    };
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("gov0000", &[]),
+        update_config,
+    );
+    match res {
+        Err(StdError::GenericErr { msg, .. }) => {
+            assert_eq!(msg, "distribution schedule must end after it starts")
+        }
+        _ => panic!("Must return generic error"),
+    }
+}
+
+#[test]
+fn test_add_and_extend_schedule() {
+    let mut deps = mock_dependencies_with_tax(&[]);
+    deps.querier.with_token_balances(&[(
+        &"reward0000".to_string(),
+        &[(&MOCK_CONTRACT_ADDR.to_string(), &Uint128::from(2000000u128))],
+    )]);
+
+    let msg = InstantiateMsg {
+        staking_token: "staking0000".to_string(),
+        rewards: vec![RewardAsset {
+            info: AssetInfo::Token {
+                contract_addr: "reward0000".to_string(),
+            },
+            distribution_schedule: vec![DistributionSchedule {
+                start_time: mock_env().block.time.seconds(),
+                end_time: mock_env().block.time.seconds() + 100,
+                amount: Uint128::from(1000000u128),
+            }],
+        }],
+        governance: "gov0000".to_string(),
+        guardian: None,
+        unbonding_period: 0,
+        boost_curve: vec![],
+        reject_underfunded_schedules: false,
+        schedule_timelock: 0,
+    };
+
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "addr0000".to_string(),
+        amount: Uint128::from(100u128),
+        msg: to_binary(&Cw20HookMsg::Bond {
+            lock_duration: None,
+            beneficiary: None,
+        })
+        .unwrap(),
+    });
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("staking0000", &[]),
+        msg,
+    )
+    .unwrap();
+
+    let mut env = mock_env();
+    env.block.time = env.block.time.plus_seconds(50);
+    let gov_info = mock_info("gov0000", &[]);
+    let reward = AssetInfo::Token {
+        contract_addr: "reward0000".to_string(),
+    };
+
+    let msg = ExecuteMsg::AddDistributionSchedule {
+        reward: reward.clone(),
+        schedule: DistributionSchedule {
+            start_time: mock_env().block.time.seconds() + 10,
+            end_time: mock_env().block.time.seconds() + 200,
+            amount: Uint128::from(1000000u128),
+        },
+    };
+    let res = execute(deps.as_mut(), env.clone(), gov_info.clone(), msg);
+    match res {
+        Err(StdError::GenericErr { msg, .. }) => {
+            assert_eq!(msg, "cannot add a distribution that has already started")
+        }
+        _ => panic!("Must return generic error"),
+    }
+
+    // 500000 accrued and 500000 left on the first slot plus the new one
+    let msg = ExecuteMsg::AddDistributionSchedule {
+        reward: reward.clone(),
+        schedule: DistributionSchedule {
+            start_time: mock_env().block.time.seconds() + 100,
+            end_time: mock_env().block.time.seconds() + 200,
+            amount: Uint128::from(1000000u128),
+        },
+    };
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("addr0000", &[]),
+        msg.clone(),
+    );
+    match res {
+        Err(StdError::GenericErr { msg, .. }) => assert_eq!(msg, "unauthorized"),
+        _ => panic!("Must return generic error"),
+    }
+    let res = execute(deps.as_mut(), env.clone(), gov_info.clone(), msg).unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "add_distribution_schedule"),
+            attr("reward_token", "reward0000"),
+            attr("amount", "1000000"),
+        ]
+    );
+
+    let msg = ExecuteMsg::AddDistributionSchedule {
+        reward: reward.clone(),
+        schedule: DistributionSchedule {
+            start_time: mock_env().block.time.seconds() + 200,
+            end_time: mock_env().block.time.seconds() + 300,
+            amount: Uint128::from(1u128),
+        },
+    };
+    let res = execute(deps.as_mut(), env.clone(), gov_info.clone(), msg);
+    match res {
+        Err(StdError::GenericErr { msg, .. }) => assert_eq!(
+            msg,
+            "reward balance does not cover the distribution schedule"
+        ),
+        _ => panic!("Must return generic error"),
+    }
+
+    // the started slot is cut and its remainder spread until the new end
+    let msg = ExecuteMsg::ExtendSchedule {
+        reward,
+        index: 0,
+        new_end: mock_env().block.time.seconds() + 150,
+        extra_amount: Uint128::zero(),
+    };
+    let _res = execute(deps.as_mut(), env.clone(), gov_info, msg).unwrap();
+
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::Config {}).unwrap();
+    let config: ConfigResponse = from_binary(&res).unwrap();
+    assert_eq!(
+        config.rewards[0].distribution_schedule,
+        vec![
+            DistributionSchedule {
+                start_time: mock_env().block.time.seconds(),
+                end_time: mock_env().block.time.seconds() + 50,
+                amount: Uint128::from(500000u128),
+            },
+            DistributionSchedule {
+                start_time: mock_env().block.time.seconds() + 100,
+                end_time: mock_env().block.time.seconds() + 200,
+                amount: Uint128::from(1000000u128),
+            },
+            DistributionSchedule {
+                start_time: mock_env().block.time.seconds() + 50,
+                end_time: mock_env().block.time.seconds() + 150,
+                amount: Uint128::from(500000u128),
+            },
+        ]
+    );
+
+    env.block.time = env.block.time.plus_seconds(100);
+    let res = query(
+        deps.as_ref(),
+        env.clone(),
+        QueryMsg::StakerInfo {
+            staker: "addr0000".to_string(),
+            block_time: Some(env.block.time.seconds()),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        from_binary::<StakerInfoResponse>(&res)
+            .unwrap()
+            .pending_rewards,
+        vec![("reward0000".to_string(), Uint128::from(1500000u128))]
+    );
+}
+
+#[test]
+fn test_query_solvency() {
+    let mut deps = mock_dependencies_with_tax(&[]);
+    deps.querier.with_token_balances(&[(
+        &"reward0000".to_string(),
+        &[(&MOCK_CONTRACT_ADDR.to_string(), &Uint128::from(1000000u128))],
+    )]);
+
+    let msg = InstantiateMsg {
+        staking_token: "staking0000".to_string(),
+        rewards: vec![RewardAsset {
+            info: AssetInfo::Token {
+                contract_addr: "reward0000".to_string(),
+            },
+            distribution_schedule: vec![DistributionSchedule {
+                start_time: mock_env().block.time.seconds(),
+                end_time: mock_env().block.time.seconds() + 100,
+                amount: Uint128::from(1000000u128),
+            }],
+        }],
+        governance: "gov0000".to_string(),
+        guardian: None,
+        unbonding_period: 0,
+        boost_curve: vec![],
+        reject_underfunded_schedules: true,
+        schedule_timelock: 0,
+    };
+
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "addr0000".to_string(),
+        amount: Uint128::from(100u128),
+        msg: to_binary(&Cw20HookMsg::Bond {
+            lock_duration: None,
+            beneficiary: None,
+        })
+        .unwrap(),
+    });
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("staking0000", &[]),
+        msg,
+    )
+    .unwrap();
+
+    let mut env = mock_env();
+    env.block.time = env.block.time.plus_seconds(30);
+    assert_eq!(
+        from_binary::<SolvencyResponse>(
+            &query(deps.as_ref(), env.clone(), QueryMsg::Solvency {}).unwrap()
+        )
+        .unwrap(),
+        SolvencyResponse {
+            rewards: vec![RewardSolvencyResponse {
+                reward_token: "reward0000".to_string(),
+                balance: Uint128::from(1000000u128),
+                principal_amount: Uint128::zero(),
+                undistributed_amount: Uint128::from(700000u128),
+                unclaimed_amount: Uint128::from(300000u128),
+                solvent: true,
+            }],
+        }
+    );
+
+    // withdrawn rewards are no longer owed
+    let msg = ExecuteMsg::Withdraw {
+        staker: None,
+        recipient: None,
+    };
+    let _res = execute(deps.as_mut(), env.clone(), mock_info("addr0000", &[]), msg).unwrap();
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::Solvency {}).unwrap();
+    assert_eq!(
+        from_binary::<SolvencyResponse>(&res).unwrap().rewards[0].unclaimed_amount,
+        Uint128::zero()
+    );
+
+    // adding a slot the balance cannot cover is refused
+    let update_config = UpdateConfig {
+        schedule_timelock: None,
+        guardian: None,
+        rewards: Some(vec![RewardAsset {
+            info: AssetInfo::Token {
+                contract_addr: "reward0000".to_string(),
+            },
+            distribution_schedule: vec![
+                DistributionSchedule {
+                    start_time: mock_env().block.time.seconds(),
+                    end_time: mock_env().block.time.seconds() + 100,
+                    amount: Uint128::from(1000000u128),
+                },
+                DistributionSchedule {
+                    start_time: mock_env().block.time.seconds() + 100,
+                    end_time: mock_env().block.time.seconds() + 200,
+                    amount: Uint128::from(1000000u128),
+                },
+            ],
+        }]),
+        boost_curve: None,
+        reject_underfunded_schedules: None,
+    };
+    let res = execute(deps.as_mut(), env, mock_info("gov0000", &[]), update_config);
+    match res {
+        Err(StdError::GenericErr { msg, .. }) => assert_eq!(
+            msg,
+            "reward balance does not cover the distribution schedule"
+        ),
+        _ => panic!("Must return generic error"),
+    }
+}
+
+#[test]
+fn test_fund() {
+    let mut deps = mock_dependencies(&[]);
+
+    let msg = InstantiateMsg {
+        staking_token: "staking0000".to_string(),
+        rewards: vec![RewardAsset {
+            info: AssetInfo::Token {
+                contract_addr: "reward0000".to_string(),
+            },
+            distribution_schedule: vec![DistributionSchedule {
+                start_time: mock_env().block.time.seconds(),
+                end_time: mock_env().block.time.seconds() + 100,
+                amount: Uint128::from(1000000u128),
+            }],
+        }],
+        governance: "gov0000".to_string(),
+        guardian: None,
+        unbonding_period: 0,
+        boost_curve: vec![],
+        reject_underfunded_schedules: false,
+        schedule_timelock: 0,
+    };
+
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "addr0000".to_string(),
+        amount: Uint128::from(100u128),
+        msg: to_binary(&Cw20HookMsg::Bond {
+            lock_duration: None,
+            beneficiary: None,
+        })
+        .unwrap(),
+    });
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("staking0000", &[]),
+        msg,
+    )
+    .unwrap();
+
+    let mut env = mock_env();
+    env.block.time = env.block.time.plus_seconds(150);
+    let fund_msg = |start_time: u64| {
+        ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: "partner0000".to_string(),
+            amount: Uint128::from(500u128),
+            msg: to_binary(&Cw20HookMsg::Fund {
+                start_time,
+                duration: 100,
+            })
+            .unwrap(),
+        })
+    };
+
+    // only reward tokens can fund
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("staking0000", &[]),
+        fund_msg(env.block.time.seconds()),
+    );
+    match res {
+        Err(StdError::GenericErr { msg, .. }) => assert_eq!(msg, "unauthorized"),
+        _ => panic!("Must return generic error"),
+    }
+
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("reward0000", &[]),
+        fund_msg(env.block.time.seconds() - 1),
+    );
+    match res {
+        Err(StdError::GenericErr { msg, .. }) => {
+            assert_eq!(msg, "cannot fund a distribution in the past")
+        }
+        _ => panic!("Must return generic error"),
+    }
+
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("reward0000", &[]),
+        fund_msg(env.block.time.seconds()),
+    )
+    .unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "fund"),
+            attr("funder", "partner0000"),
+            attr("reward_token", "reward0000"),
+            attr("amount", "500"),
+            attr("start_time", env.block.time.seconds().to_string()),
+            attr("end_time", (env.block.time.seconds() + 100).to_string()),
+        ]
+    );
+
+    // funded slots are listed apart from the governance schedule
+    let funded_slot = DistributionSchedule {
+        start_time: env.block.time.seconds(),
+        end_time: env.block.time.seconds() + 100,
+        amount: Uint128::from(500u128),
+    };
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap();
+    let config = from_binary::<ConfigResponse>(&res).unwrap();
+    assert_eq!(
+        config.rewards[0].distribution_schedule,
+        vec![DistributionSchedule {
+            start_time: mock_env().block.time.seconds(),
+            end_time: mock_env().block.time.seconds() + 100,
+            amount: Uint128::from(1000000u128),
+        }]
+    );
+    assert_eq!(
+        config.funded_slots,
+        vec![FundedSlotResponse {
+            reward_token: "reward0000".to_string(),
+            funder: "partner0000".to_string(),
+            slot: funded_slot.clone(),
+        }]
+    );
+
+    // the first slot plus half of the funded one
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::StakerInfo {
+            staker: "addr0000".to_string(),
+            block_time: Some(env.block.time.seconds() + 50),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        from_binary::<StakerInfoResponse>(&res)
+            .unwrap()
+            .pending_rewards,
+        vec![("reward0000".to_string(), Uint128::from(1000250u128))]
+    );
+
+    // fill the remaining slots at the same emission rate
+    for _ in 1..20 {
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("reward0000", &[]),
+            ExecuteMsg::Receive(Cw20ReceiveMsg {
+                sender: "filler0000".to_string(),
+                amount: Uint128::from(500u128),
+                msg: to_binary(&Cw20HookMsg::Fund {
+                    start_time: env.block.time.seconds(),
+                    duration: 100,
+                })
+                .unwrap(),
+            }),
+        )
+        .unwrap();
+    }
+
+    env.block.time = env.block.time.plus_seconds(50);
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("reward0000", &[]),
+        fund_msg(env.block.time.seconds()),
+    );
+    match res {
+        Err(StdError::GenericErr { msg, .. }) => assert_eq!(
+            msg,
+            "too many active distribution slots; emission rate too low to replace one"
+        ),
+        _ => panic!("Must return generic error"),
+    }
+
+    // a faster slot evicts the slowest one and refunds its funder
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("reward0000", &[]),
+        ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: "partner0001".to_string(),
+            amount: Uint128::from(1000u128),
+            msg: to_binary(&Cw20HookMsg::Fund {
+                start_time: env.block.time.seconds(),
+                duration: 100,
+            })
+            .unwrap(),
+        }),
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "reward0000".to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: "partner0000".to_string(),
+                amount: Uint128::from(250u128),
+            })
+            .unwrap(),
+            funds: vec![],
+        }))]
+    );
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap();
+    let funded_slots = from_binary::<ConfigResponse>(&res).unwrap().funded_slots;
+    assert_eq!(funded_slots.len(), 20);
+    assert!(funded_slots
+        .iter()
+        .all(|funded| funded.funder != "partner0000"));
+}
+
+#[test]
+fn test_query_reward_rate() {
+    let mut deps = mock_dependencies(&[]);
+    let start = mock_env().block.time.seconds();
+
+    let msg = InstantiateMsg {
+        staking_token: "staking0000".to_string(),
+        rewards: vec![
+            RewardAsset {
+                info: AssetInfo::Token {
+                    contract_addr: "staking0000".to_string(),
+                },
+                distribution_schedule: vec![
+                    DistributionSchedule {
+                        start_time: start,
+                        end_time: start + 100,
+                        amount: Uint128::from(1000u128),
+                    },
+                    DistributionSchedule {
+                        start_time: start + 50,
+                        end_time: start + 150,
+                        amount: Uint128::from(500u128),
+                    },
+                ],
+            },
+            RewardAsset {
+                info: AssetInfo::NativeToken {
+                    denom: "uusd".to_string(),
+                },
+                distribution_schedule: vec![],
+            },
+        ],
+        governance: "gov0000".to_string(),
+        guardian: None,
+        unbonding_period: 0,
+        boost_curve: vec![],
+        reject_underfunded_schedules: false,
+        schedule_timelock: 0,
+    };
+
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "addr0000".to_string(),
+        amount: Uint128::from(1000000u128),
+        msg: to_binary(&Cw20HookMsg::Bond {
+            lock_duration: None,
+            beneficiary: None,
+        })
+        .unwrap(),
+    });
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("staking0000", &[]),
+        msg,
+    )
+    .unwrap();
+
+    // both slots run at 10 + 5 per second
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::RewardRate {
+            block_time: Some(start + 60),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        from_binary::<RewardRateResponse>(&res).unwrap(),
+        RewardRateResponse {
+            rewards: vec![
+                RewardAssetRateResponse {
+                    reward_token: "staking0000".to_string(),
+                    emission_per_second: Decimal256::from_ratio(15u64, 1u64),
+                    undistributed_amount: Uint128::from(400u128 + 450u128),
+                    next_change_time: Some(start + 100),
+                    apr: Some(Decimal256::from_ratio(15u64 * 31536000u64, 1000000u64)),
+                },
+                RewardAssetRateResponse {
+                    reward_token: "uusd".to_string(),
+                    emission_per_second: Decimal256::zero(),
+                    undistributed_amount: Uint128::zero(),
+                    next_change_time: None,
+                    apr: None,
+                },
+            ]
+        }
+    );
+
+    // defaults to the current block time, before the second slot starts
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::RewardRate { block_time: None },
+    )
+    .unwrap();
+    let rate = from_binary::<RewardRateResponse>(&res).unwrap().rewards[0].clone();
+    assert_eq!(
+        rate.emission_per_second,
+        Decimal256::from_ratio(10u64, 1u64)
+    );
+    assert_eq!(rate.undistributed_amount, Uint128::from(1500u128));
+    assert_eq!(rate.next_change_time, Some(start + 50));
+
+    // the remaining amount matches what compute_reward leaves to distribute
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::StakerInfo {
+            staker: "addr0000".to_string(),
+            block_time: Some(start + 60),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        from_binary::<StakerInfoResponse>(&res)
+            .unwrap()
+            .pending_rewards[0],
+        ("staking0000".to_string(), Uint128::from(650u128))
+    );
+}
+
+#[test]
+fn test_reward_index_dust() {
+    let mut deps = mock_dependencies(&[]);
+
+    let msg = InstantiateMsg {
+        staking_token: "staking0000".to_string(),
+        rewards: vec![RewardAsset {
+            info: AssetInfo::Token {
+                contract_addr: "reward0000".to_string(),
+            },
+            distribution_schedule: vec![DistributionSchedule {
+                start_time: mock_env().block.time.seconds(),
+                end_time: mock_env().block.time.seconds() + 100,
+                amount: Uint128::from(100u128),
+            }],
+        }],
+        governance: "gov0000".to_string(),
+        guardian: None,
+        unbonding_period: 0,
+        boost_curve: vec![],
+        reject_underfunded_schedules: false,
+        schedule_timelock: 0,
+    };
+
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    // one reward per second is below the index precision for this stake
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "addr0000".to_string(),
+        amount: Uint128::from(3_000_000_000_000_000_000u128),
+        msg: to_binary(&Cw20HookMsg::Bond {
+            lock_duration: None,
+            beneficiary: None,
+        })
+        .unwrap(),
+    });
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("staking0000", &[]),
+        msg,
+    )
+    .unwrap();
+
+    let mut env = mock_env();
+    for _ in 0..2 {
+        env.block.time = env.block.time.plus_seconds(1);
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("addr0000", &[]),
+            ExecuteMsg::Withdraw {
+                staker: None,
+                recipient: None,
+            },
+        )
+        .unwrap();
+        assert_eq!(res.messages, vec![]);
+    }
+
+    // the dust of the first two seconds is carried into the third
+    env.block.time = env.block.time.plus_seconds(1);
+    let res = execute(
+        deps.as_mut(),
+        env,
+        mock_info("addr0000", &[]),
+        ExecuteMsg::Withdraw {
+            staker: None,
+            recipient: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(WasmMsg::Execute {
+            contract_addr: "reward0000".to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: "addr0000".to_string(),
+                amount: Uint128::from(3u128),
+            })
+            .unwrap(),
+            funds: vec![],
+        })]
+    );
+}
+
+#[test]
+fn test_slot_rounding() {
+    let mut deps = mock_dependencies(&[]);
+
+    let msg = InstantiateMsg {
+        staking_token: "staking0000".to_string(),
+        rewards: vec![RewardAsset {
+            info: AssetInfo::Token {
+                contract_addr: "reward0000".to_string(),
+            },
+            distribution_schedule: vec![DistributionSchedule {
+                start_time: mock_env().block.time.seconds(),
+                end_time: mock_env().block.time.seconds() + 3,
+                amount: Uint128::from(10u128),
+            }],
+        }],
+        governance: "gov0000".to_string(),
+        guardian: None,
+        unbonding_period: 0,
+        boost_curve: vec![],
+        reject_underfunded_schedules: false,
+        schedule_timelock: 0,
+    };
+
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "addr0000".to_string(),
+        amount: Uint128::from(1u128),
+        msg: to_binary(&Cw20HookMsg::Bond {
+            lock_duration: None,
+            beneficiary: None,
+        })
+        .unwrap(),
+    });
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("staking0000", &[]),
+        msg,
+    )
+    .unwrap();
+
+    // 10 over 3 seconds still releases all 10 when withdrawn every second
+    let mut env = mock_env();
+    for amount in [3u128, 3u128, 4u128].iter() {
+        env.block.time = env.block.time.plus_seconds(1);
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("addr0000", &[]),
+            ExecuteMsg::Withdraw {
+                staker: None,
+                recipient: None,
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            res.messages,
+            vec![SubMsg::new(WasmMsg::Execute {
+                contract_addr: "reward0000".to_string(),
+                msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: "addr0000".to_string(),
+                    amount: Uint128::from(*amount),
+                })
+                .unwrap(),
+                funds: vec![],
+            })]
+        );
+    }
+}
+
+#[test]
+fn test_governance_transfer() {
+    let mut deps = mock_dependencies(&[]);
+
+    let msg = InstantiateMsg {
+        staking_token: "staking0000".to_string(),
+        rewards: vec![],
+        governance: "gov0000".to_string(),
+        guardian: None,
+        unbonding_period: 0,
+        boost_curve: vec![],
+        reject_underfunded_schedules: false,
+        schedule_timelock: 0,
+    };
+
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    let msg = ExecuteMsg::ProposeGovernance {
+        governance: "gov0001".to_string(),
+    };
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        msg.clone(),
+    );
+    match res {
+        Err(StdError::GenericErr { msg, .. }) => assert_eq!(msg, "unauthorized"),
+        _ => panic!("Must return generic error"),
+    }
+    let _res = execute(deps.as_mut(), mock_env(), mock_info("gov0000", &[]), msg).unwrap();
+
+    // only the nominee can accept
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        ExecuteMsg::AcceptGovernance {},
+    );
+    match res {
+        Err(StdError::GenericErr { msg, .. }) => assert_eq!(msg, "unauthorized"),
+        _ => panic!("Must return generic error"),
+    }
+
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("gov0000", &[]),
+        ExecuteMsg::CancelGovernanceProposal {},
+    )
+    .unwrap();
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("gov0001", &[]),
+        ExecuteMsg::AcceptGovernance {},
+    );
+    match res {
+        Err(StdError::GenericErr { msg, .. }) => assert_eq!(msg, "unauthorized"),
+        _ => panic!("Must return generic error"),
+    }
+
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("gov0000", &[]),
+        ExecuteMsg::ProposeGovernance {
+            governance: "gov0001".to_string(),
+        },
+    )
+    .unwrap();
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::PendingGovernance {}).unwrap();
+    assert_eq!(
+        from_binary::<PendingGovernanceResponse>(&res).unwrap(),
+        PendingGovernanceResponse {
+            governance: "gov0000".to_string(),
+            pending_governance: Some("gov0001".to_string()),
+            schedule_timelock: 0,
+            schedule_changes: vec![],
+        }
+    );
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("gov0001", &[]),
+        ExecuteMsg::AcceptGovernance {},
+    )
+    .unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "accept_governance"),
+            attr("governance", "gov0001")
+        ]
+    );
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap();
+    let config: ConfigResponse = from_binary(&res).unwrap();
+    assert_eq!(config.governance, "gov0001".to_string());
+    assert_eq!(config.pending_governance, None);
+}
+
+#[test]
+fn test_schedule_timelock() {
+    let mut deps = mock_dependencies_with_tax(&[]);
+    deps.querier.with_token_balances(&[(
+        &"reward0000".to_string(),
+        &[(&MOCK_CONTRACT_ADDR.to_string(), &Uint128::from(2000000u128))],
+    )]);
+
+    let msg = InstantiateMsg {
+        staking_token: "staking0000".to_string(),
+        rewards: vec![RewardAsset {
+            info: AssetInfo::Token {
+                contract_addr: "reward0000".to_string(),
+            },
+            distribution_schedule: vec![DistributionSchedule {
+                start_time: mock_env().block.time.seconds(),
+                end_time: mock_env().block.time.seconds() + 100,
+                amount: Uint128::from(1000000u128),
+            }],
+        }],
+        governance: "gov0000".to_string(),
+        guardian: None,
+        unbonding_period: 0,
+        boost_curve: vec![],
+        reject_underfunded_schedules: false,
+        schedule_timelock: 1000,
+    };
+
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    let gov_info = mock_info("gov0000", &[]);
+    let reward = AssetInfo::Token {
+        contract_addr: "reward0000".to_string(),
+    };
+    let schedule = DistributionSchedule {
+        start_time: mock_env().block.time.seconds() + 2000,
+        end_time: mock_env().block.time.seconds() + 3000,
+        amount: Uint128::from(500000u128),
+    };
+
+    // slots must not start before the change can be executed
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        gov_info.clone(),
+        ExecuteMsg::AddDistributionSchedule {
+            reward: reward.clone(),
+            schedule: DistributionSchedule {
+                start_time: mock_env().block.time.seconds() + 1000,
+                end_time: mock_env().block.time.seconds() + 3000,
+                amount: Uint128::from(500000u128),
+            },
+        },
+    );
+    match res {
+        Err(StdError::GenericErr { msg, .. }) => {
+            assert_eq!(msg, "distribution schedule must start after the timelock")
+        }
+        _ => panic!("Must return generic error"),
+    }
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        gov_info.clone(),
+        ExecuteMsg::AddDistributionSchedule {
+            reward: reward.clone(),
+            schedule: schedule.clone(),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "add_distribution_schedule"),
+            attr("schedule_change_id", "0"),
+        ]
+    );
+
+    // the timelock change itself waits for the current timelock
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        gov_info.clone(),
+        UpdateConfig {
+            guardian: None,
+            rewards: None,
+            boost_curve: None,
+            reject_underfunded_schedules: None,
+            schedule_timelock: Some(0),
+        },
+    )
+    .unwrap();
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::PendingGovernance {}).unwrap();
+    assert_eq!(
+        from_binary::<PendingGovernanceResponse>(&res).unwrap(),
+        PendingGovernanceResponse {
+            governance: "gov0000".to_string(),
+            pending_governance: None,
+            schedule_timelock: 1000,
+            schedule_changes: vec![
+                ScheduleChangeResponse {
+                    id: 0,
+                    change: ScheduleChange::AddDistributionSchedule {
+                        reward: reward.clone(),
+                        schedule: schedule.clone(),
+                    },
+                    eta: mock_env().block.time.seconds() + 1000,
+                },
+                ScheduleChangeResponse {
+                    id: 1,
+                    change: ScheduleChange::UpdateTimelock {
+                        schedule_timelock: 0,
+                    },
+                    eta: mock_env().block.time.seconds() + 1000,
+                },
+            ],
+        }
+    );
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        ExecuteMsg::ExecuteScheduleChange { id: 0 },
+    );
+    match res {
+        Err(StdError::GenericErr { msg, .. }) => {
+            assert_eq!(msg, "schedule change is still timelocked")
+        }
+        _ => panic!("Must return generic error"),
+    }
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        ExecuteMsg::CancelScheduleChange { id: 1 },
+    );
+    match res {
+        Err(StdError::GenericErr { msg, .. }) => assert_eq!(msg, "unauthorized"),
+        _ => panic!("Must return generic error"),
+    }
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        gov_info,
+        ExecuteMsg::CancelScheduleChange { id: 1 },
+    )
+    .unwrap();
+
+    // anyone can execute once the timelock has passed
+    let mut env = mock_env();
+    env.block.time = env.block.time.plus_seconds(1000);
+    let _res = execute(
+        deps.as_mut(),
+        env,
+        mock_info("addr0000", &[]),
+        ExecuteMsg::ExecuteScheduleChange { id: 0 },
+    )
+    .unwrap();
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap();
+    let config: ConfigResponse = from_binary(&res).unwrap();
+    assert_eq!(config.rewards[0].distribution_schedule[1], schedule);
+    assert_eq!(config.schedule_timelock, 1000);
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::PendingGovernance {}).unwrap();
+    assert_eq!(
+        from_binary::<PendingGovernanceResponse>(&res)
+            .unwrap()
+            .schedule_changes,
+        vec![]
+    );
+
+    // a queued change cannot be executed long after its eta
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("gov0000", &[]),
+        UpdateConfig {
+            guardian: None,
+            rewards: None,
+            boost_curve: None,
+            reject_underfunded_schedules: None,
+            schedule_timelock: Some(500),
+        },
+    )
+    .unwrap();
+    let mut env = mock_env();
+    env.block.time = env.block.time.plus_seconds(1000 + 14 * 24 * 60 * 60 + 1);
+    let res = execute(
+        deps.as_mut(),
+        env,
+        mock_info("addr0000", &[]),
+        ExecuteMsg::ExecuteScheduleChange { id: 2 },
+    );
+    match res {
+        Err(StdError::GenericErr { msg, .. }) => assert_eq!(msg, "schedule change has expired"),
+        _ => panic!("Must return generic error"),
+    }
+}
+
+#[test]
+fn test_migrate_positions() {
+    let mut deps = mock_dependencies(&[]);
+
+    let msg = InstantiateMsg {
+        staking_token: "staking0000".to_string(),
+        rewards: vec![
+            RewardAsset {
+                info: AssetInfo::Token {
+                    contract_addr: "staking0000".to_string(),
+                },
+                distribution_schedule: vec![DistributionSchedule {
+                    start_time: mock_env().block.time.seconds(),
+                    end_time: mock_env().block.time.seconds() + 100,
+                    amount: Uint128::from(1000u128),
+                }],
+            },
+            RewardAsset {
+                info: AssetInfo::Token {
+                    contract_addr: "reward0000".to_string(),
+                },
+                distribution_schedule: vec![DistributionSchedule {
+                    start_time: mock_env().block.time.seconds(),
+                    end_time: mock_env().block.time.seconds() + 100,
+                    amount: Uint128::from(2000u128),
+                }],
+            },
+        ],
+        governance: "gov0000".to_string(),
+        guardian: None,
+        unbonding_period: 0,
+        boost_curve: vec![],
+        reject_underfunded_schedules: false,
+        schedule_timelock: 0,
+    };
+
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    let bond_msg = |staker: &str| {
+        ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: staker.to_string(),
+            amount: Uint128::from(100u128),
+            msg: to_binary(&Cw20HookMsg::Bond {
+                lock_duration: None,
+                beneficiary: None,
+            })
+            .unwrap(),
+        })
+    };
+    for staker in ["addr0000", "addr0001"].iter() {
+        let _res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("staking0000", &[]),
+            bond_msg(staker),
+        )
+        .unwrap();
+    }
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        ExecuteMsg::MigratePosition {},
+    );
+    match res {
+        Err(StdError::GenericErr { msg, .. }) => {
+            assert_eq!(msg, "staking contract has not been migrated")
+        }
+        _ => panic!("Must return generic error"),
+    }
+
+    let mut env = mock_env();
+    env.block.time = env.block.time.plus_seconds(50);
+    let _res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("gov0000", &[]),
+        ExecuteMsg::MigrateStaking {
+            new_staking_contract: "newstaking0000".to_string(),
+        },
+    )
+    .unwrap();
+
+    // the old contract takes no more bonds
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("staking0000", &[]),
+        bond_msg("addr0000"),
+    );
+    match res {
+        Err(StdError::GenericErr { msg, .. }) => {
+            assert_eq!(msg, "staking contract has been migrated")
+        }
+        _ => panic!("Must return generic error"),
+    }
+
+    // the staking token reward is bonded along with the principal
+    let migrated_bond_msg = |staker: &str, amount: u128| {
+        SubMsg::new(WasmMsg::Execute {
+            contract_addr: "staking0000".to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Send {
+                contract: "newstaking0000".to_string(),
+                amount: Uint128::from(amount),
+                msg: to_binary(&Cw20HookMsg::Bond {
+                    lock_duration: None,
+                    beneficiary: Some(staker.to_string()),
+                })
+                .unwrap(),
+            })
+            .unwrap(),
+            funds: vec![],
+        })
+    };
+    let reward_msg = |staker: &str| {
+        SubMsg::new(WasmMsg::Execute {
+            contract_addr: "reward0000".to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: staker.to_string(),
+                amount: Uint128::from(500u128),
+            })
+            .unwrap(),
+            funds: vec![],
+        })
+    };
+
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("addr0000", &[]),
+        ExecuteMsg::MigratePosition {},
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![reward_msg("addr0000"), migrated_bond_msg("addr0000", 350)]
+    );
+
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("addr0000", &[]),
+        ExecuteMsg::MigratePosition {},
+    );
+    match res {
+        Err(StdError::GenericErr { msg, .. }) => assert_eq!(msg, "Nothing bonded"),
+        _ => panic!("Must return generic error"),
+    }
+
+    // a keeper moves the remaining stakers
+    let res = execute(
+        deps.as_mut(),
+        env,
+        mock_info("keeper0000", &[]),
+        ExecuteMsg::MigratePositions { limit: None },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![reward_msg("addr0001"), migrated_bond_msg("addr0001", 350)]
+    );
+
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::State { block_time: None },
+    )
+    .unwrap();
+    let state: StateResponse = from_binary(&res).unwrap();
+    assert_eq!(state.total_bond_amount, Uint128::zero());
+    assert_eq!(state.total_weight, Uint128::zero());
+}
+
+#[test]
+fn test_migrate_versions() {
+    let mut deps = mock_dependencies(&[]);
+    let env = mock_env();
+
+    set_contract_version(&mut deps.storage, "crates.io:cw20-base", "0.0.1").unwrap();
+    let res = migrate(
+        deps.as_mut(),
+        env.clone(),
+        MigrateMsg {
+            seconds_per_block: None,
+        },
+    );
+    match res {
+        Err(StdError::GenericErr { msg, .. }) => {
+            assert_eq!(msg, "cannot migrate from contract crates.io:cw20-base")
+        }
+        _ => panic!("Must return generic error"),
+    }
+
+    set_contract_version(&mut deps.storage, "crates.io:staking", "99.0.0").unwrap();
+    let res = migrate(
+        deps.as_mut(),
+        env.clone(),
+        MigrateMsg {
+            seconds_per_block: None,
+        },
+    );
+    match res {
+        Err(StdError::GenericErr { msg, .. }) => {
+            assert_eq!(msg, "cannot migrate from newer version 99.0.0")
+        }
+        _ => panic!("Must return generic error"),
+    }
+
+    // a block-based deployment from before version tracking
+    CONTRACT.remove(&mut deps.storage);
+    let height = env.block.height;
+    singleton(&mut deps.storage, b"config")
+        .save(&LegacyConfig {
+            reward_token: deps.api.addr_canonicalize("reward0000").unwrap(),
+            staking_token: deps.api.addr_canonicalize("staking0000").unwrap(),
+            distribution_schedule: vec![(height - 100, height + 100, Uint128::from(2400u128))],
+            governance: deps.api.addr_canonicalize("gov0000").unwrap(),
+            unbonding_period: 0,
+        })
+        .unwrap();
+    singleton(&mut deps.storage, b"state")
+        .save(&LegacyState {
+            last_distributed: height - 50,
+            total_bond_amount: Uint128::from(100u128),
+            global_reward_index: Decimal::zero(),
+        })
+        .unwrap();
+    Bucket::new(&mut deps.storage, b"reward")
+        .save(
+            deps.api.addr_canonicalize("addr0000").unwrap().as_slice(),
+            &LegacyStakerInfo {
+                reward_index: Decimal::zero(),
+                bond_amount: Uint128::from(100u128),
+                pending_reward: Uint128::zero(),
+            },
+        )
+        .unwrap();
+
+    let res = migrate(
+        deps.as_mut(),
+        env.clone(),
+        MigrateMsg {
+            seconds_per_block: Some(Decimal::from_ratio(6u128, 1u128)),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "migrate"),
+            attr("from_version", "0.0.0"),
+            attr("to_version", "0.1.0"),
+        ]
+    );
+    assert_eq!(
+        get_contract_version(&deps.storage).unwrap().contract,
+        "crates.io:staking"
+    );
+
+    let now = env.block.time.seconds();
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::Config {}).unwrap();
+    assert_eq!(
+        from_binary::<ConfigResponse>(&res).unwrap().rewards[0].distribution_schedule,
+        vec![DistributionSchedule {
+            start_time: now - 600,
+            end_time: now + 600,
+            amount: Uint128::from(2400u128),
+        }]
+    );
+
+    // 50 settled blocks and another quarter of the slot in time
+    let res = query(
+        deps.as_ref(),
+        env,
+        QueryMsg::StakerInfo {
+            staker: "addr0000".to_string(),
+            block_time: Some(now + 300),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        from_binary::<StakerInfoResponse>(&res)
+            .unwrap()
+            .pending_rewards,
+        vec![("reward0000".to_string(), Uint128::from(1200u128))]
+    );
+}
+
+#[test]
+fn test_slash() {
+    let mut deps = mock_dependencies(&[]);
+
+    let msg = InstantiateMsg {
+        staking_token: "staking0000".to_string(),
+        rewards: vec![RewardAsset {
+            info: AssetInfo::Token {
+                contract_addr: "reward0000".to_string(),
+            },
+            distribution_schedule: vec![],
+        }],
+        governance: "gov0000".to_string(),
+        guardian: None,
+        unbonding_period: 0,
+        boost_curve: vec![],
+        reject_underfunded_schedules: false,
+        schedule_timelock: 0,
+    };
+
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    // addr0000 bonds 100 and addr0001 bonds 300 tokens
+    for (staker, amount) in [("addr0000", 100u128), ("addr0001", 300u128)].iter() {
+        let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: staker.to_string(),
+            amount: Uint128::from(*amount),
+            msg: to_binary(&Cw20HookMsg::Bond {
+                lock_duration: None,
+                beneficiary: None,
+            })
+            .unwrap(),
+        });
+        let info = mock_info("staking0000", &[]);
+        let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    }
+
+    // only governance can slash
+    let msg = ExecuteMsg::Slash {
+        staker: Some("addr0000".to_string()),
+        ratio: Decimal::percent(50),
+        recipient: Some("insurance0000".to_string()),
+    };
+    let info = mock_info("addr0000", &[]);
+    let res = execute(deps.as_mut(), mock_env(), info, msg.clone()).unwrap_err();
+    match res {
+        StdError::GenericErr { msg, .. } => assert_eq!(msg, "unauthorized"),
+        _ => panic!("Must return generic error"),
+    }
+
+    // half of addr0000's bond goes to the insurance pool
+    let info = mock_info("gov0000", &[]);
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "staking0000".to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: "insurance0000".to_string(),
+                amount: Uint128::from(50u128),
+            })
+            .unwrap(),
+            funds: vec![],
+        }))]
+    );
+
+    // ratio above one is rejected; a pool-wide slash must leave something
+    for ratio in [Decimal::percent(101), Decimal::one()].iter() {
+        let msg = ExecuteMsg::Slash {
+            staker: None,
+            ratio: *ratio,
+            recipient: None,
+        };
+        let info = mock_info("gov0000", &[]);
+        let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+    }
+
+    // burn 20% of every bond; 350 bonded tokens become 280
+    let msg = ExecuteMsg::Slash {
+        staker: None,
+        ratio: Decimal::percent(20),
+        recipient: None,
+    };
+    let info = mock_info("gov0000", &[]);
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "staking0000".to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Burn {
+                amount: Uint128::from(70u128),
+            })
+            .unwrap(),
+            funds: vec![],
+        }))]
+    );
+
+    let state: StateResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::State { block_time: None },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(state.total_bond_amount, Uint128::from(280u128));
+    assert_eq!(state.share_price, Decimal256::percent(80));
+
+    for (staker, amount) in [("addr0000", 40u128), ("addr0001", 240u128)].iter() {
+        let res: StakerInfoResponse = from_binary(
+            &query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::StakerInfo {
+                    staker: staker.to_string(),
+                    block_time: None,
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(res.bond_amount, Uint128::from(*amount));
+    }
+
+    // the slashed bond can no longer be unbonded
+    let msg = ExecuteMsg::Unbond {
+        amount: Uint128::from(241u128),
+    };
+    let info = mock_info("addr0001", &[]);
+    let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+
+    let msg = ExecuteMsg::Unbond {
+        amount: Uint128::from(240u128),
+    };
+    let info = mock_info("addr0001", &[]);
+    let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    // new bonds buy shares at the lowered price
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "addr0000".to_string(),
+        amount: Uint128::from(80u128),
+        msg: to_binary(&Cw20HookMsg::Bond {
+            lock_duration: None,
+            beneficiary: None,
+        })
+        .unwrap(),
+    });
+    let info = mock_info("staking0000", &[]);
+    let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    let res: StakerInfoResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::StakerInfo {
+                staker: "addr0000".to_string(),
+                block_time: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(res.bond_amount, Uint128::from(120u128));
 }
@@ -0,0 +1,129 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use cosmwasm_std::{
+    to_binary, Addr, Api, BankMsg, CanonicalAddr, Coin, CosmosMsg, Decimal, QuerierWrapper,
+    StdResult, Uint128, WasmMsg,
+};
+use cw20::Cw20ExecuteMsg;
+use terra_cosmwasm::TerraQuerier;
+
+/// Same JSON layout as the terraswap asset types, so messages and stored
+/// assets stay interchangeable with terraswap contracts
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Asset {
+    pub info: AssetInfo,
+    pub amount: Uint128,
+}
+
+impl fmt::Display for Asset {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}{}", self.amount, self.info)
+    }
+}
+
+static DECIMAL_FRACTION: Uint128 = Uint128::new(1_000_000_000_000_000_000u128);
+
+impl Asset {
+    /// Terra tax paid on a native transfer of this asset; luna is exempt
+    pub fn compute_tax(&self, querier: &QuerierWrapper) -> StdResult<Uint128> {
+        match &self.info {
+            AssetInfo::NativeToken { denom } if denom != "uluna" => {
+                let terra_querier = TerraQuerier::new(querier);
+                let tax_rate: Decimal = terra_querier.query_tax_rate()?.rate;
+                let tax_cap: Uint128 = terra_querier.query_tax_cap(denom.to_string())?.cap;
+                Ok(std::cmp::min(
+                    self.amount.checked_sub(self.amount.multiply_ratio(
+                        DECIMAL_FRACTION,
+                        DECIMAL_FRACTION * tax_rate + DECIMAL_FRACTION,
+                    ))?,
+                    tax_cap,
+                ))
+            }
+            _ => Ok(Uint128::zero()),
+        }
+    }
+
+    /// transfer of the asset to `recipient`, less tax for native assets
+    pub fn into_msg(self, querier: &QuerierWrapper, recipient: Addr) -> StdResult<CosmosMsg> {
+        let amount = self.amount;
+        match &self.info {
+            AssetInfo::Token { contract_addr } => Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: contract_addr.to_string(),
+                msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: recipient.to_string(),
+                    amount,
+                })?,
+                funds: vec![],
+            })),
+            AssetInfo::NativeToken { denom } => Ok(CosmosMsg::Bank(BankMsg::Send {
+                to_address: recipient.to_string(),
+                amount: vec![Coin {
+                    denom: denom.to_string(),
+                    amount: amount.checked_sub(self.compute_tax(querier)?)?,
+                }],
+            })),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AssetInfo {
+    Token { contract_addr: String },
+    NativeToken { denom: String },
+}
+
+impl fmt::Display for AssetInfo {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AssetInfo::NativeToken { denom } => write!(f, "{}", denom),
+            AssetInfo::Token { contract_addr } => write!(f, "{}", contract_addr),
+        }
+    }
+}
+
+impl AssetInfo {
+    pub fn to_raw(&self, api: &dyn Api) -> StdResult<AssetInfoRaw> {
+        match self {
+            AssetInfo::NativeToken { denom } => Ok(AssetInfoRaw::NativeToken {
+                denom: denom.to_string(),
+            }),
+            AssetInfo::Token { contract_addr } => Ok(AssetInfoRaw::Token {
+                contract_addr: api.addr_canonicalize(contract_addr)?,
+            }),
+        }
+    }
+
+    pub fn is_native_token(&self) -> bool {
+        matches!(self, AssetInfo::NativeToken { .. })
+    }
+
+    pub fn equal(&self, asset: &AssetInfo) -> bool {
+        self == asset
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub enum AssetInfoRaw {
+    Token { contract_addr: CanonicalAddr },
+    NativeToken { denom: String },
+}
+
+impl AssetInfoRaw {
+    pub fn to_normal(&self, api: &dyn Api) -> StdResult<AssetInfo> {
+        match self {
+            AssetInfoRaw::NativeToken { denom } => Ok(AssetInfo::NativeToken {
+                denom: denom.to_string(),
+            }),
+            AssetInfoRaw::Token { contract_addr } => Ok(AssetInfo::Token {
+                contract_addr: api.addr_humanize(contract_addr)?.to_string(),
+            }),
+        }
+    }
+
+    pub fn equal(&self, asset: &AssetInfoRaw) -> bool {
+        self == asset
+    }
+}
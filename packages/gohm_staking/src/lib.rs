@@ -1,3 +1,4 @@
+pub mod asset;
 pub mod querier;
 pub mod reward_token;
 pub mod staking;
@@ -1,7 +1,34 @@
 use cosmwasm_bignumber::{Decimal256, Uint256};
-use cosmwasm_std::{Coin, Deps, StdResult};
+use cosmwasm_std::{
+    to_binary, Addr, BalanceResponse, BankQuery, Coin, Deps, QueryRequest, StdResult, Uint128,
+    WasmQuery,
+};
+use cw20::{BalanceResponse as Cw20BalanceResponse, Cw20QueryMsg};
 use terra_cosmwasm::TerraQuerier;
 
+pub fn query_balance(deps: Deps, account_addr: Addr, denom: String) -> StdResult<Uint128> {
+    let balance: BalanceResponse = deps.querier.query(&QueryRequest::Bank(BankQuery::Balance {
+        address: account_addr.to_string(),
+        denom,
+    }))?;
+    Ok(balance.amount.amount)
+}
+
+pub fn query_token_balance(
+    deps: Deps,
+    contract_addr: Addr,
+    account_addr: Addr,
+) -> StdResult<Uint128> {
+    let balance: Cw20BalanceResponse =
+        deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+            contract_addr: contract_addr.to_string(),
+            msg: to_binary(&Cw20QueryMsg::Balance {
+                address: account_addr.to_string(),
+            })?,
+        }))?;
+    Ok(balance.balance)
+}
+
 pub fn query_tax_rate(deps: Deps) -> StdResult<Decimal256> {
     let terra_querier = TerraQuerier::new(&deps.querier);
     Ok(terra_querier.query_tax_rate()?.rate.into())
@@ -1,7 +1,9 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use cosmwasm_std::{Decimal, StdError, StdResult};
+use crate::asset::{Asset, AssetInfo};
+use cosmwasm_std::{Binary, Coin, Decimal, StdError, StdResult, Uint128};
+use cw20::{Cw20ReceiveMsg, Expiration};
 
 /// TokenContract InstantiateMsg
 #[derive(Serialize, Deserialize, JsonSchema)]
@@ -10,10 +12,10 @@ pub struct InstantiateMsg {
     pub symbol: String,
     pub decimals: u8,
     pub minter: String,
-    pub gohm_token: String,
-    pub denom: String,
-    pub gohm_rate: Decimal,
-    pub denom_rate: Decimal,
+    /// Assets pulled in on mint and paid out on burn
+    pub basket: Vec<BasketAsset>,
+    /// Allowed to update the rates
+    pub admin: Option<String>,
 }
 
 impl InstantiateMsg {
@@ -32,10 +34,28 @@ impl InstantiateMsg {
         if self.decimals > 18 {
             return Err(StdError::generic_err("Decimals must not exceed 18"));
         }
+        if self.basket.is_empty() {
+            return Err(StdError::generic_err("Basket must not be empty"));
+        }
+        for (i, asset) in self.basket.iter().enumerate() {
+            if self.basket[..i]
+                .iter()
+                .any(|other| other.info.equal(&asset.info))
+            {
+                return Err(StdError::generic_err("Duplicate basket asset"));
+            }
+        }
         Ok(())
     }
 }
 
+/// A backing asset and the amount of it per reward token
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BasketAsset {
+    pub info: AssetInfo,
+    pub rate: Decimal,
+}
+
 fn is_valid_name(name: &str) -> bool {
     let bytes = name.as_bytes();
     if bytes.len() < 3 || bytes.len() > 50 {
@@ -57,5 +77,160 @@ fn is_valid_symbol(symbol: &str) -> bool {
     true
 }
 
+/// cw20 operations plus the reward token's own
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    Transfer {
+        recipient: String,
+        amount: Uint128,
+    },
+    /// Burn tokens and receive the backing assets as set by the redemption mode
+    Burn {
+        amount: Uint128,
+    },
+    Send {
+        contract: String,
+        amount: Uint128,
+        msg: Binary,
+    },
+    /// Minter operation; pulls in the backing assets at the configured rates
+    Mint {
+        recipient: String,
+        amount: Uint128,
+    },
+    /// Mint from a basket cw20 sent by the minter, see `Cw20HookMsg`
+    Receive(Cw20ReceiveMsg),
+    IncreaseAllowance {
+        spender: String,
+        amount: Uint128,
+        expires: Option<Expiration>,
+    },
+    DecreaseAllowance {
+        spender: String,
+        amount: Uint128,
+        expires: Option<Expiration>,
+    },
+    TransferFrom {
+        owner: String,
+        recipient: String,
+        amount: Uint128,
+    },
+    SendFrom {
+        owner: String,
+        contract: String,
+        amount: Uint128,
+        msg: Binary,
+    },
+    BurnFrom {
+        owner: String,
+        amount: Uint128,
+    },
+    /// Admin operation to change the rates of future mints and burns;
+    /// assets that are not in the basket yet are appended
+    UpdateRates {
+        basket: Vec<BasketAsset>,
+    },
+    /// Admin operation to choose how burns are paid out
+    SetRedemptionMode {
+        mode: RedemptionMode,
+    },
+    /// Credit the sent native basket coins to the sender for a later
+    /// `MintHook`, e.g. earlier in the same transaction
+    DepositNative {},
+    /// Return the sender's deposited coins that no mint has used
+    WithdrawDeposits {},
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Cw20HookMsg {
+    /// Mint as many whole reward tokens as the sent tokens back at their
+    /// basket rate and return the rest, without an allowance. Native basket
+    /// assets are taken from the sender's `DepositNative` balance and other
+    /// basket cw20s are still pulled in with `TransferFrom`
+    MintHook { recipient: String },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum RedemptionMode {
+    /// `amount * rate` of each asset, capped at the burned share of the reserve
+    Fixed,
+    /// `amount / total_supply` of each reserve actually held
+    ProRata,
+}
+
+/// cw20 queries plus the reward token's own
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    Balance {
+        address: String,
+    },
+    TokenInfo {},
+    Minter {},
+    Allowance {
+        owner: String,
+        spender: String,
+    },
+    AllAllowances {
+        owner: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    AllAccounts {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Reserves held against the total supply
+    Backing {},
+    /// Assets a burn of `amount` would pay out in the current redemption mode
+    RedemptionPreview {
+        amount: Uint128,
+    },
+    /// Native coins deposited by `address` and not yet used by a mint
+    Deposits {
+        address: String,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MigrateMsg {
+    /// Replaces the admin, e.g. for deployments instantiated without one
+    pub admin: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BackingResponse {
+    pub total_supply: Uint128,
+    pub assets: Vec<AssetBackingResponse>,
+    /// Lowest balance / required ratio over the backing assets;
+    /// None while nothing is required
+    pub collateral_ratio: Option<Decimal>,
+    /// True when no backing asset has a shortfall
+    pub fully_backed: bool,
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
-pub struct MigrateMsg {}
+pub struct RedemptionPreviewResponse {
+    pub mode: RedemptionMode,
+    /// Amounts before tax on native assets
+    pub assets: Vec<Asset>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AssetBackingResponse {
+    pub info: AssetInfo,
+    pub rate: Decimal,
+    /// Held by the reward token contract
+    pub balance: Uint128,
+    /// Paid out if the whole supply was burned at `rate`
+    pub required: Uint128,
+    pub shortfall: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct DepositsResponse {
+    pub deposits: Vec<Coin>,
+}
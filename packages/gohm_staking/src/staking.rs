@@ -1,15 +1,30 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use std::fmt;
 
+use crate::asset::AssetInfo;
+use cosmwasm_bignumber::Decimal256;
 use cosmwasm_std::{Decimal, Uint128};
 use cw20::Cw20ReceiveMsg;
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct InstantiateMsg {
-    pub reward_token: String,
     pub staking_token: String,
-    pub distribution_schedule: Vec<(u64, u64, Uint128)>,
+    pub rewards: Vec<RewardAsset>,
     pub governance: String,
+    /// Address allowed to pause operations besides governance
+    pub guardian: Option<String>,
+    /// Seconds unbonded tokens stay locked before they can be claimed;
+    /// zero sends them back immediately on unbond
+    pub unbonding_period: u64,
+    /// (minimum lock duration in seconds, reward weight multiplier) steps,
+    /// ordered by lock duration; empty disables locking
+    pub boost_curve: Vec<(u64, Decimal)>,
+    /// Refuse schedules the contract does not hold enough rewards for
+    pub reject_underfunded_schedules: bool,
+    /// Seconds governance schedule changes wait in the queue before they
+    /// can be executed; zero applies them right away
+    pub schedule_timelock: u64,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -19,29 +34,192 @@ pub enum ExecuteMsg {
     Unbond {
         amount: Uint128,
     },
-    /// Withdraw pending rewards
-    Withdraw {},
+    /// Release unbonded tokens whose unbonding period has passed
+    Claim {},
+    /// Return the whole bond and unbonding queue of the executor right away,
+    /// skipping locks; rewards earned so far stay pending. Only while unbond
+    /// is paused and governance has enabled it
+    EmergencyUnbond {},
+    /// Governance operation to allow or stop EmergencyUnbond
+    SetEmergencyUnbond {
+        enabled: bool,
+    },
+    /// Guardian or governance operation to halt one operation
+    Pause {
+        operation: Operation,
+    },
+    /// Governance operation to resume a paused operation
+    Unpause {
+        operation: Operation,
+    },
+    /// Governance operation to take `ratio` of one staker's bond, or of
+    /// every bond pro rata when `staker` is None. Slashed tokens go to
+    /// `recipient` or are burned; unbonding amounts are not slashed
+    Slash {
+        staker: Option<String>,
+        ratio: Decimal,
+        recipient: Option<String>,
+    },
+    /// Move part of the executor's bond to `recipient` without unbonding;
+    /// pending rewards stay with their current owners
+    TransferPosition {
+        recipient: String,
+        amount: Uint128,
+    },
+    /// Reset the boost of a staker whose lock has ended; callable by anyone
+    ExpireLock {
+        staker: String,
+    },
+    /// Withdraw pending rewards of `staker` (the executor by default).
+    /// Operators of the staker can only pay the staker or one of the
+    /// staker's whitelisted recipients
+    Withdraw {
+        staker: Option<String>,
+        recipient: Option<String>,
+    },
+    /// Allow `operator` to withdraw rewards on behalf of the executor
+    ApproveOperator {
+        operator: String,
+    },
+    RevokeOperator {
+        operator: String,
+    },
+    /// Allow operators to send the executor's rewards to `recipient`
+    AddRecipient {
+        recipient: String,
+    },
+    RemoveRecipient {
+        recipient: String,
+    },
+    /// Bond the pending reward paid in the staking token
+    Compound {},
+    /// Compound the staking token reward whenever the staker's rewards are settled
+    SetAutoCompound {
+        auto_compound: bool,
+    },
+    /// Governance operation to append a distribution slot to an existing
+    /// reward asset; the slot must start in the future and be funded
+    AddDistributionSchedule {
+        reward: AssetInfo,
+        schedule: DistributionSchedule,
+    },
+    /// Governance operation to push back the end of slot `index` of `reward`
+    /// and distribute `extra_amount` on top of what is left of it. A started
+    /// slot is cut at the current time and the rest is appended as a new slot
+    ExtendSchedule {
+        reward: AssetInfo,
+        index: u32,
+        new_end: u64,
+        extra_amount: Uint128,
+    },
     /// Owner operation to stop distribution on current staking contract
-    /// and send remaining tokens to the new contract
+    /// and send remaining tokens to the new contract; what funded slots have
+    /// not released goes back to their funders. The contract then refuses
+    /// new bonds and schedules; stakers can still unbond and withdraw.
+    /// Running slots are cut right away, bypassing the schedule timelock
     MigrateStaking {
         new_staking_contract: String,
     },
+    /// Once migrated, bond the executor's position and staking token reward
+    /// in the new contract; other rewards are paid to the executor
+    MigratePosition {},
+    /// MigratePosition for up to `limit` stakers; callable by anyone
+    MigratePositions {
+        limit: Option<u32>,
+    },
+    /// Every existing reward asset must stay in `rewards`;
+    /// new reward assets can be appended.
+    /// With a schedule timelock, `rewards` and `schedule_timelock` are
+    /// queued while the other fields apply right away
     UpdateConfig {
-        governance: Option<String>,
-        distribution_schedule: Option<Vec<(u64, u64, Uint128)>>,
+        guardian: Option<String>,
+        rewards: Option<Vec<RewardAsset>>,
+        /// Only applies to locks made after the update
+        boost_curve: Option<Vec<(u64, Decimal)>>,
+        reject_underfunded_schedules: Option<bool>,
+        schedule_timelock: Option<u64>,
+    },
+    /// Governance operation to nominate the next governance,
+    /// which takes over once it accepts
+    ProposeGovernance {
+        governance: String,
+    },
+    /// Executed by the nominated governance to take over
+    AcceptGovernance {},
+    CancelGovernanceProposal {},
+    /// Apply a queued schedule change whose timelock has passed;
+    /// callable by anyone up to 14 days after its eta
+    ExecuteScheduleChange {
+        id: u64,
+    },
+    /// Governance operation to drop a queued schedule change
+    CancelScheduleChange {
+        id: u64,
+    },
+}
+
+/// Governance schedule change waiting for the schedule timelock
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ScheduleChange {
+    AddDistributionSchedule {
+        reward: AssetInfo,
+        schedule: DistributionSchedule,
+    },
+    /// `slot` is the slot as it was queued, so later changes
+    /// to the schedule cannot redirect the extension
+    ExtendSchedule {
+        reward: AssetInfo,
+        slot: DistributionSchedule,
+        new_end: u64,
+        extra_amount: Uint128,
+    },
+    UpdateRewards {
+        rewards: Vec<RewardAsset>,
+    },
+    UpdateTimelock {
+        schedule_timelock: u64,
     },
 }
 
+/// CW20 token or native denom paid to stakers along its own distribution schedule
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RewardAsset {
+    pub info: AssetInfo,
+    pub distribution_schedule: Vec<DistributionSchedule>,
+}
+
+/// `amount` released linearly from `start_time` to `end_time`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, JsonSchema)]
+pub struct DistributionSchedule {
+    pub start_time: u64,
+    pub end_time: u64,
+    pub amount: Uint128,
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum Cw20HookMsg {
-    Bond {},
+    /// Bond the received tokens, optionally locking the whole position
+    /// for `lock_duration` seconds in exchange for a reward boost.
+    /// The bond is credited to `beneficiary` when given, otherwise to
+    /// the sender of the tokens
+    Bond {
+        lock_duration: Option<u64>,
+        beneficiary: Option<String>,
+    },
+    /// Sent by a reward token to distribute the received amount over
+    /// `duration` seconds from `start_time`; open to anyone
+    Fund { start_time: u64, duration: u64 },
 }
 
-/// migrate struct for distribution schedule
-/// block-based schedule to a time-based schedule
+/// Storage migrations run for every version between the stored and the
+/// current one. `seconds_per_block` is only for deployments whose schedule
+/// and last distribution are block heights; they are converted to times
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
-pub struct MigrateMsg {}
+pub struct MigrateMsg {
+    pub seconds_per_block: Option<Decimal>,
+}
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
@@ -54,15 +232,143 @@ pub enum QueryMsg {
         staker: String,
         block_time: Option<u64>,
     },
+    Unbondings {
+        staker: String,
+    },
+    AllStakers {
+        start_after: Option<String>,
+        limit: Option<u32>,
+        block_time: Option<u64>,
+    },
+    WithdrawPermissions {
+        staker: String,
+    },
+    PauseStatus {},
+    Solvency {},
+    /// Emission rates at `block_time`, now when not given
+    RewardRate {
+        block_time: Option<u64>,
+    },
+    /// Governance nomination and queued schedule changes
+    PendingGovernance {},
+}
+
+/// Operations the guardian can pause one by one
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Operation {
+    /// Bonding, including compounding
+    Bond,
+    /// Unbonding, claiming and position transfers
+    Unbond,
+    Withdraw,
+    MigrateStaking,
+}
+
+impl fmt::Display for Operation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Operation::Bond => write!(f, "bond"),
+            Operation::Unbond => write!(f, "unbond"),
+            Operation::Withdraw => write!(f, "withdraw"),
+            Operation::MigrateStaking => write!(f, "migrate_staking"),
+        }
+    }
 }
 
 // We define a custom struct for each query response
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct ConfigResponse {
-    pub reward_token: String,
     pub staking_token: String,
-    pub distribution_schedule: Vec<(u64, u64, Uint128)>,
+    pub rewards: Vec<RewardAsset>,
     pub governance: String,
+    pub guardian: Option<String>,
+    pub unbonding_period: u64,
+    pub boost_curve: Vec<(u64, Decimal)>,
+    pub reject_underfunded_schedules: bool,
+    pub pending_governance: Option<String>,
+    pub schedule_timelock: u64,
+    /// New staking contract once MigrateStaking was executed
+    pub migrated_to: Option<String>,
+    /// Slots paid through `Fund`, apart from the governance schedules
+    pub funded_slots: Vec<FundedSlotResponse>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct FundedSlotResponse {
+    pub reward_token: String,
+    pub funder: String,
+    pub slot: DistributionSchedule,
+}
+
+// We define a custom struct for each query response
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PendingGovernanceResponse {
+    pub governance: String,
+    /// Nominated governance that has not accepted yet
+    pub pending_governance: Option<String>,
+    pub schedule_timelock: u64,
+    pub schedule_changes: Vec<ScheduleChangeResponse>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ScheduleChangeResponse {
+    pub id: u64,
+    pub change: ScheduleChange,
+    /// Time from which the change can be executed
+    pub eta: u64,
+}
+
+// We define a custom struct for each query response
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RewardRateResponse {
+    pub rewards: Vec<RewardAssetRateResponse>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RewardAssetRateResponse {
+    /// reward token address or denom
+    pub reward_token: String,
+    /// Summed over the slots running at the block time
+    pub emission_per_second: Decimal256,
+    /// Left to distribute after the block time
+    pub undistributed_amount: Uint128,
+    /// Next start or end of a slot, none once the schedule is over
+    pub next_change_time: Option<u64>,
+    /// Yearly emission over the total bond amount; only given when the
+    /// reward is the staking token, as other assets would need a price
+    pub apr: Option<Decimal256>,
+}
+
+// We define a custom struct for each query response
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SolvencyResponse {
+    pub rewards: Vec<RewardSolvencyResponse>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RewardSolvencyResponse {
+    /// reward token address or denom
+    pub reward_token: String,
+    pub balance: Uint128,
+    /// Bonded and unbonding tokens held in the balance when the reward
+    /// is the staking token itself
+    pub principal_amount: Uint128,
+    /// Left to distribute by the schedule
+    pub undistributed_amount: Uint128,
+    /// Distributed to stakers but not withdrawn yet
+    pub unclaimed_amount: Uint128,
+    pub solvent: bool,
+}
+
+// We define a custom struct for each query response
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PauseStatusResponse {
+    pub bond: bool,
+    pub unbond: bool,
+    pub withdraw: bool,
+    pub migrate_staking: bool,
+    pub emergency_unbond: bool,
 }
 
 // We define a custom struct for each query response
@@ -70,14 +376,53 @@ pub struct ConfigResponse {
 pub struct StateResponse {
     pub last_distributed: u64,
     pub total_bond_amount: Uint128,
-    pub global_reward_index: Decimal,
+    /// Sum of boosted bond amounts that rewards are distributed over
+    pub total_weight: Uint128,
+    /// (reward token address or denom, global_reward_index)
+    pub global_reward_indexes: Vec<(String, Decimal256)>,
+    /// Staking tokens a bond share is worth; pool-wide slashes lower it
+    pub share_price: Decimal256,
 }
 
 // We define a custom struct for each query response
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct StakerInfoResponse {
     pub staker: String,
-    pub reward_index: Decimal,
+    /// (reward token address or denom, reward_index)
+    pub reward_indexes: Vec<(String, Decimal256)>,
     pub bond_amount: Uint128,
-    pub pending_reward: Uint128,
+    /// (reward token address or denom, pending_reward)
+    pub pending_rewards: Vec<(String, Uint128)>,
+    pub boost: Decimal,
+    /// Time the position can be unbonded again; zero when not locked
+    pub lock_end: u64,
+    pub auto_compound: bool,
+}
+
+// We define a custom struct for each query response
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AllStakersResponse {
+    pub stakers: Vec<StakerInfoResponse>,
+}
+
+// We define a custom struct for each query response
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct UnbondingsResponse {
+    pub staker: String,
+    pub unbondings: Vec<UnbondingResponse>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct UnbondingResponse {
+    pub amount: Uint128,
+    pub release_time: u64,
+    pub matured: bool,
+}
+
+// We define a custom struct for each query response
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct WithdrawPermissionsResponse {
+    pub staker: String,
+    pub operators: Vec<String>,
+    pub recipients: Vec<String>,
 }